@@ -7,7 +7,7 @@ fn main() -> io::Result<()> {
         c.handleGreetAndStartReading().await?;
 
         let _ = c.Login(env!("EMAIL"), env!("PASS")).await?;
-        let _ = c.List("\"\"", "*").await?;
+        let _ = c.List("", "*").await?;
         let selected = c.Select("Inbox", false).await?;
 
         println!("{:#?}", selected);
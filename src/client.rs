@@ -1,20 +1,26 @@
 use std::{
     borrow::{BorrowMut, Cow},
+    collections::BTreeMap,
     io,
     net::SocketAddr,
 };
 
 use async_native_tls::{Host, TlsStream};
-use futures_lite::{
+use futures_lite::{AsyncRead, AsyncReadExt, AsyncWrite, AsyncWriteExt};
+use smol::{
     io::{ReadHalf, WriteHalf},
-    AsyncRead, AsyncWrite, AsyncWriteExt,
+    net::{AsyncToSocketAddrs, TcpStream},
 };
-use smol::net::{AsyncToSocketAddrs, TcpStream};
 
 use crate::{
+    commands::StoreMode,
+    flag::Flag,
     read::TY,
-    response::{self, DataResp},
-    responses::{Login, Select},
+    response::{self, AppendUid, DataResp},
+    responses::{
+        fetch, resync, select::MailboxStatus, Login, MailboxAttribute, MailboxInfo, Message, Select,
+    },
+    sorting, threading, utf7, Rights, SequenceSet,
 };
 
 use super::{
@@ -25,6 +31,9 @@ use super::{
     Reader,
 };
 
+/// How many messages `SortMessages` and `ThreadMessages` fetch at a time.
+const fetchBatch: u32 = 500;
+
 #[derive(Debug)]
 pub struct ConnInfo {
     LocalAddr: SocketAddr,
@@ -38,6 +47,20 @@ pub struct Client<'a, T: AsyncRead + AsyncWrite + Unpin> {
     pub Writer: WriteHalf<T>,
     pub State: ConnState,
     pub capabilities: Cow<'a, [TY<'a>]>,
+    /// The currently selected mailbox, if any.
+    pub Mailbox: Option<MailboxStatus<'a>>,
+    /// What is known about the messages of the selected mailbox, by sequence
+    /// number. Kept up to date from the FETCH responses of every command.
+    pub Messages: BTreeMap<u32, Message<'a>>,
+    /// Mailboxes seen in LIST and LSUB responses, kept up to date by the
+    /// mailbox management commands.
+    pub Mailboxes: responses::List<'a>,
+    /// Extensions turned on with ENABLE (RFC 5161), as reported by the
+    /// server.
+    pub Enabled: Vec<String>,
+    /// What the server said about itself in reply to ID (RFC 2971), e.g. its
+    /// `name` and `version`.
+    pub ServerId: Option<BTreeMap<String, String>>,
 }
 
 impl<'a> Client<'a, TlsStream<TcpStream>> {
@@ -55,18 +78,30 @@ impl<'a> Client<'a, TlsStream<TcpStream>> {
         let local_adder = stream.get_ref().local_addr()?;
         let peer_addr = stream.get_ref().peer_addr()?;
 
+        Ok(Client::New(stream, local_adder, peer_addr))
+    }
+}
+
+impl<'a, T: AsyncRead + AsyncWrite + Unpin + Send> Client<'a, T> {
+    /// Wraps an established connection, before the server greeting is read.
+    pub(crate) fn New(stream: T, local: SocketAddr, peer: SocketAddr) -> Client<'a, T> {
         let (r, w) = smol::io::split(stream);
 
-        Ok(Client::<'a, TlsStream<TcpStream>> {
+        Client {
             Inner: ConnInfo {
-                LocalAddr: local_adder,
-                PeerAddr: peer_addr,
+                LocalAddr: local,
+                PeerAddr: peer,
             },
             Reader: Reader::from(r),
             Writer: w,
             State: ConnState::LogoutState,
             capabilities: Default::default(),
-        })
+            Mailbox: None,
+            Messages: Default::default(),
+            Mailboxes: Default::default(),
+            Enabled: Default::default(),
+            ServerId: None,
+        }
     }
 
     pub async fn handleGreetAndStartReading<'b>(&'b mut self) -> io::Result<()> {
@@ -111,66 +146,1568 @@ impl<'a> Client<'a, TlsStream<TcpStream>> {
             .1)
     }
 
+    /// Selects a mailbox, or examines it with `readOnly`. On servers with
+    /// ACL, the user's rights are fetched into `MailboxStatus.MyRights`.
     pub async fn Select(
         &mut self,
         name: &'a str,
         readOnly: bool,
     ) -> io::Result<(responses::Select<'a>, Resp<'a>)> {
-        let mut select = responses::Select::default();
+        let (resync, resp) = self.selectMailbox(name, readOnly, false, None).await?;
+        Ok((
+            responses::Select {
+                Mailbox: resync.Mailbox,
+            },
+            resp,
+        ))
+    }
+
+    /// Selects a mailbox like `Select`, turning on CONDSTORE (RFC 7162) so
+    /// that `MailboxStatus.HighestModSeq` is known.
+    pub async fn SelectCondStore(
+        &mut self,
+        name: &'a str,
+        readOnly: bool,
+    ) -> io::Result<(responses::Select<'a>, Resp<'a>)> {
+        self.requireCondStore()?;
+        let (resync, resp) = self.selectMailbox(name, readOnly, true, None).await?;
+        Ok((
+            responses::Select {
+                Mailbox: resync.Mailbox,
+            },
+            resp,
+        ))
+    }
+
+    /// Selects a mailbox like `Select`, and gets what changed since the
+    /// session described by `qresync` (RFC 7162): the messages whose flags
+    /// changed, and the UIDs expunged. QRESYNC is enabled first if needed.
+    ///
+    /// A `UidValidity` that no longer matches means the server sends no
+    /// changes, and everything known about the mailbox must be dropped.
+    pub async fn SelectQResync(
+        &mut self,
+        name: &'a str,
+        readOnly: bool,
+        qresync: &commands::QResync<'_>,
+    ) -> io::Result<(responses::Resync<'a>, Resp<'a>)> {
+        self.enableQResync().await?;
+        self.selectMailbox(name, readOnly, false, Some(qresync))
+            .await
+    }
+
+    async fn selectMailbox(
+        &mut self,
+        name: &'a str,
+        readOnly: bool,
+        condStore: bool,
+        qresync: Option<&commands::QResync<'_>>,
+    ) -> io::Result<(responses::Resync<'a>, Resp<'a>)> {
+        let mut select = responses::Resync::default();
         select.Mailbox.Name = Cow::Borrowed(name);
         select.Mailbox.ReadOnly = readOnly;
 
-        let selected = self
+        // Responses to SELECT are about the new mailbox
+        self.Messages.clear();
+        self.Mailbox = None;
+
+        let encoded = self.mailboxName(name);
+        let mut selected = self
             .execute(
                 commands::Select {
-                    Mailbox: name,
+                    Mailbox: &encoded,
                     ReadOnly: readOnly,
+                    CondStore: condStore,
+                    QResync: qresync.copied(),
                 },
                 select,
             )
             .await?;
 
+        // The rights are optional, the mailbox is selected even if MYRIGHTS
+        // fails
+        if selected.1.IsOk() && self.HasCapability("ACL") {
+            if let Ok((acl, resp)) = self
+                .execute(
+                    commands::MyRights { Mailbox: &encoded },
+                    responses::Acl {
+                        Utf8: self.IsEnabled("UTF8=ACCEPT"),
+                        ..Default::default()
+                    },
+                )
+                .await
+            {
+                if resp.IsOk() {
+                    selected.0.Mailbox.MyRights = acl.MyRights;
+                }
+            }
+        }
+
+        if selected.1.IsOk() {
+            self.Mailbox = Some(selected.0.Mailbox.clone());
+            self.State = ConnState::SelectedState;
+        } else {
+            self.Messages.clear();
+            self.State = ConnState::AuthenticatedState;
+        }
+
         Ok(selected)
     }
 
+    async fn enableQResync(&mut self) -> io::Result<()> {
+        if !self.HasCapability("QRESYNC") {
+            return Err(io::Error::new(
+                io::ErrorKind::Other,
+                "server does not support QRESYNC",
+            ));
+        }
+
+        if !self.IsEnabled("QRESYNC") {
+            self.Enable(&["QRESYNC"]).await?.1.Check()?;
+        }
+
+        Ok(())
+    }
+
+    /// Asks for the status of a mailbox without selecting it.
+    pub async fn Status(
+        &mut self,
+        name: &str,
+        items: &[commands::StatusItem],
+    ) -> io::Result<(responses::Status<'a>, Resp<'a>)> {
+        for item in items {
+            if let Some(capability) = item.Capability() {
+                if !self.supports(capability) {
+                    return Err(io::Error::new(
+                        io::ErrorKind::Other,
+                        format!("STATUS {} requires {}", item.Name(), capability),
+                    ));
+                }
+            }
+        }
+
+        let encoded = self.mailboxName(name);
+        self.execute(
+            commands::Status {
+                Mailbox: &encoded,
+                Items: items,
+            },
+            responses::Status {
+                Utf8: self.IsEnabled("UTF8=ACCEPT"),
+                ..Default::default()
+            },
+        )
+        .await
+    }
+
+    /// Changes the flags of messages. `\Recent` and `\*` can't be stored
+    /// and fail with a `flag::FlagError` before anything is sent.
+    pub async fn Store(
+        &mut self,
+        seqSet: &SequenceSet,
+        uid: bool,
+        mode: StoreMode,
+        silent: bool,
+        flags: &[Flag],
+    ) -> io::Result<(responses::Fetch<'a>, Resp<'a>)> {
+        Flag::CheckStorable(flags)?;
+        self.execute(
+            commands::Store {
+                SeqSet: seqSet,
+                Uid: uid,
+                Mode: mode,
+                Silent: silent,
+                Flags: flags,
+                UnchangedSince: None,
+            },
+            responses::Fetch::default(),
+        )
+        .await
+    }
+
+    /// Changes the flags of the messages that haven't changed since
+    /// `modSeq` (RFC 7162). The others are left alone and reported by
+    /// `StatusResp::Modified`, or in `ErrorCode::Modified` when none could
+    /// be changed.
+    pub async fn StoreUnchangedSince(
+        &mut self,
+        seqSet: &SequenceSet,
+        uid: bool,
+        mode: StoreMode,
+        silent: bool,
+        flags: &[Flag],
+        modSeq: u64,
+    ) -> io::Result<(responses::Fetch<'a>, Resp<'a>)> {
+        self.requireCondStore()?;
+        Flag::CheckStorable(flags)?;
+        self.execute(
+            commands::Store {
+                SeqSet: seqSet,
+                Uid: uid,
+                Mode: mode,
+                Silent: silent,
+                Flags: flags,
+                UnchangedSince: Some(modSeq),
+            },
+            responses::Fetch::default(),
+        )
+        .await
+    }
+
+    pub async fn Fetch(
+        &mut self,
+        seqSet: &SequenceSet,
+        uid: bool,
+        items: &[commands::FetchItem<'_>],
+    ) -> io::Result<(responses::Fetch<'a>, Resp<'a>)> {
+        self.execute(
+            commands::Fetch {
+                SeqSet: seqSet,
+                Uid: uid,
+                Items: items,
+                ChangedSince: None,
+                Vanished: false,
+            },
+            responses::Fetch::default(),
+        )
+        .await
+    }
+
+    /// Fetches the messages of `seqSet` changed since `modSeq`, such as the
+    /// `HighestModSeq` of the last session (RFC 7162).
+    pub async fn FetchChangedSince(
+        &mut self,
+        seqSet: &SequenceSet,
+        uid: bool,
+        items: &[commands::FetchItem<'_>],
+        modSeq: u64,
+    ) -> io::Result<(responses::Fetch<'a>, Resp<'a>)> {
+        self.requireCondStore()?;
+        self.execute(
+            commands::Fetch {
+                SeqSet: seqSet,
+                Uid: uid,
+                Items: items,
+                ChangedSince: Some(modSeq),
+                Vanished: false,
+            },
+            responses::Fetch::default(),
+        )
+        .await
+    }
+
+    /// Gets what changed in the messages of `uids` since `modSeq`: the
+    /// messages whose flags changed, and the UIDs expunged (RFC 7162). For
+    /// resynchronizing a mailbox selected without QRESYNC, or a part of it.
+    pub async fn FetchVanished(
+        &mut self,
+        uids: &SequenceSet,
+        items: &[commands::FetchItem<'_>],
+        modSeq: u64,
+    ) -> io::Result<(responses::Resync<'a>, Resp<'a>)> {
+        self.enableQResync().await?;
+        self.execute(
+            commands::Fetch {
+                SeqSet: uids,
+                Uid: true,
+                Items: items,
+                ChangedSince: Some(modSeq),
+                Vanished: true,
+            },
+            responses::Resync::default(),
+        )
+        .await
+    }
+
+    /// Like `HasCapability`, but also counts capabilities implied by others:
+    /// QRESYNC implies CONDSTORE (RFC 7162).
+    fn supports(&self, capability: &str) -> bool {
+        self.HasCapability(capability)
+            || (capability.eq_ignore_ascii_case("CONDSTORE") && self.HasCapability("QRESYNC"))
+    }
+
+    fn requireCondStore(&self) -> io::Result<()> {
+        if self.supports("CONDSTORE") {
+            return Ok(());
+        }
+
+        Err(io::Error::new(
+            io::ErrorKind::Other,
+            "server does not support CONDSTORE",
+        ))
+    }
+
+    /// Copies messages to another mailbox. A copy that would go over quota
+    /// fails, reported by `Check` as `ErrorCode::OverQuota`.
+    pub async fn Copy(
+        &mut self,
+        seqSet: &SequenceSet,
+        uid: bool,
+        mailbox: &str,
+    ) -> io::Result<Resp<'a>> {
+        let encoded = self.mailboxName(mailbox);
+        Ok(self
+            .execute(
+                commands::Copy {
+                    SeqSet: seqSet,
+                    Uid: uid,
+                    Mailbox: &encoded,
+                },
+                responses::None,
+            )
+            .await?
+            .1)
+    }
+
+    /// Moves messages to another mailbox. When the server lacks the MOVE
+    /// capability, this falls back to COPY, marking the messages \Deleted and
+    /// expunging them. The expunge is limited to the moved messages with
+    /// `UID EXPUNGE` when moving by UID on a server with UIDPLUS, otherwise a
+    /// plain EXPUNGE also removes any other message already marked \Deleted.
+    pub async fn Move(
+        &mut self,
+        seqSet: &SequenceSet,
+        uid: bool,
+        mailbox: &str,
+    ) -> io::Result<(responses::Expunge<'a>, Resp<'a>)> {
+        if self.HasCapability("MOVE") {
+            let encoded = self.mailboxName(mailbox);
+            return self
+                .execute(
+                    commands::Move {
+                        SeqSet: seqSet,
+                        Uid: uid,
+                        Mailbox: &encoded,
+                    },
+                    responses::Expunge::default(),
+                )
+                .await;
+        }
+
+        let copied = self.Copy(seqSet, uid, mailbox).await?;
+        if !copied.IsOk() {
+            return Ok((Default::default(), copied));
+        }
+
+        let (_, stored) = self
+            .Store(seqSet, uid, StoreMode::Add, true, &[Flag::Deleted])
+            .await?;
+        if !stored.IsOk() {
+            return Ok((Default::default(), stored));
+        }
+
+        let uids = if uid && self.HasCapability("UIDPLUS") {
+            Some(seqSet)
+        } else {
+            None
+        };
+
+        let (mut expunged, resp) = self.Expunge(uids).await?;
+        if let Resp::StatResp(status) = copied {
+            expunged.CopyUid = status.CopyUid();
+        }
+
+        Ok((expunged, resp))
+    }
+
+    /// Permanently removes the messages marked \Deleted. With `uids`, sends
+    /// `UID EXPUNGE` (RFC 4315) so only those messages are removed, leaving
+    /// the ones other clients marked alone.
+    pub async fn Expunge(
+        &mut self,
+        uids: Option<&SequenceSet>,
+    ) -> io::Result<(responses::Expunge<'a>, Resp<'a>)> {
+        if uids.is_some() && !self.HasCapability("UIDPLUS") {
+            return Err(io::Error::new(
+                io::ErrorKind::Other,
+                "UID EXPUNGE requires the UIDPLUS capability",
+            ));
+        }
+
+        self.execute(
+            commands::Expunge { Uids: uids },
+            responses::Expunge::default(),
+        )
+        .await
+    }
+
+    /// Appends a message to a mailbox. With `create`, a mailbox the server
+    /// reports missing with `[TRYCREATE]` is created and the APPEND retried.
+    /// A full mailbox is reported by `Check` as `ErrorCode::OverQuota`.
+    pub async fn Append(
+        &mut self,
+        mailbox: &str,
+        flags: &[Flag],
+        date: Option<&str>,
+        message: &[u8],
+        create: bool,
+    ) -> io::Result<Resp<'a>> {
+        let encoded = self.mailboxName(mailbox);
+        let utf8 = self.IsEnabled("UTF8=ACCEPT");
+        let append = || commands::Append {
+            Mailbox: &encoded,
+            Flags: flags,
+            Date: date,
+            Message: commands::Literal::Bytes(message.into()),
+            Utf8: utf8,
+        };
+
+        let (_, resp) = self.execute(append(), responses::None).await?;
+        if create && self.ShouldCreate(mailbox, &resp).await? {
+            return Ok(self.execute(append(), responses::None).await?.1);
+        }
+
+        Ok(resp)
+    }
+
+    /// Appends several messages in one command with MULTIAPPEND (RFC 3502),
+    /// so that either all of them are stored or none is. Without the
+    /// capability the messages are appended one by one, stopping at the first
+    /// failure, and the ones before it stay appended.
+    ///
+    /// Returns the UIDs of the appended messages when the server reports them.
+    pub async fn AppendMany(
+        &mut self,
+        mailbox: &str,
+        messages: &[commands::AppendMessage<'_>],
+        create: bool,
+    ) -> io::Result<(Option<AppendUid>, Resp<'a>)> {
+        if self.HasCapability("MULTIAPPEND") {
+            let encoded = self.mailboxName(mailbox);
+            let utf8 = self.IsEnabled("UTF8=ACCEPT");
+            let append = || commands::MultiAppend {
+                Mailbox: &encoded,
+                Messages: messages,
+                Utf8: utf8,
+            };
+
+            let (_, mut resp) = self.execute(append(), responses::None).await?;
+            if create && self.ShouldCreate(mailbox, &resp).await? {
+                resp = self.execute(append(), responses::None).await?.1;
+            }
+
+            let uids = match &resp {
+                Resp::StatResp(status) => status.AppendUid(),
+                _ => None,
+            };
+            return Ok((uids, resp));
+        }
+
+        let mut uids = Some(AppendUid::default());
+        let mut resp = Resp::StatResp(Default::default());
+        for msg in messages {
+            resp = self
+                .Append(mailbox, msg.Flags, msg.Date, msg.Message, create)
+                .await?;
+            if !resp.IsOk() {
+                break;
+            }
+
+            let appended = match &resp {
+                Resp::StatResp(status) => status.AppendUid(),
+                _ => None,
+            };
+            match (uids.as_mut(), appended) {
+                (Some(uids), Some(appended))
+                    if uids.Uids.IsEmpty() || uids.UidValidity == appended.UidValidity =>
+                {
+                    uids.UidValidity = appended.UidValidity;
+                    uids.Uids.Set.extend(appended.Uids.Set);
+                }
+                // The server did not report UIDs for every message
+                _ => uids = None,
+            }
+        }
+
+        Ok((uids.filter(|u| !u.Uids.IsEmpty()), resp))
+    }
+
+    /// Appends a message made of client-sent text and messages or body parts
+    /// already on the server, using CATENATE (RFC 4469). Unresolvable URLs
+    /// and oversized results are reported by `Check` as
+    /// `ErrorCode::BadUrl` and `ErrorCode::TooBig`.
+    pub async fn Catenate(
+        &mut self,
+        mailbox: &str,
+        flags: &[Flag],
+        date: Option<&str>,
+        parts: &[commands::CatenatePart<'_>],
+    ) -> io::Result<Resp<'a>> {
+        if !self.HasCapability("CATENATE") {
+            return Err(io::Error::new(
+                io::ErrorKind::Other,
+                "server does not support CATENATE",
+            ));
+        }
+
+        let encoded = self.mailboxName(mailbox);
+        Ok(self
+            .execute(
+                commands::Catenate {
+                    Mailbox: &encoded,
+                    Flags: flags,
+                    Date: date,
+                    Parts: parts,
+                },
+                responses::None,
+            )
+            .await?
+            .1)
+    }
+
+    /// Like `Append`, reading the `size` bytes of the message from `reader`
+    /// as they are sent. The retry after `[TRYCREATE]` only happens if the
+    /// server refused the APPEND before the message was read.
+    pub async fn AppendFrom(
+        &mut self,
+        mailbox: &str,
+        flags: &[Flag],
+        date: Option<&str>,
+        size: u64,
+        reader: &mut (dyn AsyncRead + Unpin + Send),
+        create: bool,
+    ) -> io::Result<Resp<'a>> {
+        let encoded = self.mailboxName(mailbox);
+        let utf8 = self.IsEnabled("UTF8=ACCEPT");
+        let append = || commands::Append {
+            Mailbox: &encoded,
+            Flags: flags,
+            Date: date,
+            Message: commands::Literal::Stream(size),
+            Utf8: utf8,
+        };
+
+        let mut stream = Some(reader);
+        let (_, resp) = self
+            .executeWith(append(), responses::None, &mut stream)
+            .await?;
+        if create && stream.is_some() && self.ShouldCreate(mailbox, &resp).await? {
+            return Ok(self
+                .executeWith(append(), responses::None, &mut stream)
+                .await?
+                .1);
+        }
+
+        Ok(resp)
+    }
+
+    /// Creates `mailbox` if `resp` failed with `[TRYCREATE]`, and reports
+    /// whether it did.
+    async fn ShouldCreate(&mut self, mailbox: &str, resp: &Resp<'_>) -> io::Result<bool> {
+        match resp {
+            Resp::StatResp(status) if status.Type == "NO" && status.Code == "TRYCREATE" => {
+                self.Create(mailbox).await?.Check()?;
+                Ok(true)
+            }
+            _ => Ok(false),
+        }
+    }
+
     pub async fn List(
         &mut self,
         reference: &'a str,
         name: &'a str,
     ) -> io::Result<(responses::List<'a>, Resp<'a>)> {
-        Ok(self
+        self.ListExtended(reference, &[name], &[], &[]).await
+    }
+
+    /// LIST with the selection and return options of LIST-EXTENDED
+    /// (RFC 5258), matching several patterns at once. With a
+    /// `ListReturn::Status` option (RFC 5819), the status of each mailbox is
+    /// returned in `MailboxInfo.Status`.
+    pub async fn ListExtended(
+        &mut self,
+        reference: &str,
+        patterns: &[&str],
+        selection: &[commands::ListSelection],
+        returns: &[commands::ListReturn<'_>],
+    ) -> io::Result<(responses::List<'a>, Resp<'a>)> {
+        // A LIST of everything under the reference shows what was deleted
+        let everything = patterns == ["*"] && selection.is_empty();
+        let prefix = reference;
+        let reference = self.mailboxName(reference);
+        let encoded: Vec<_> = patterns.iter().map(|p| self.mailboxName(p)).collect();
+        let patterns: Vec<&str> = encoded.iter().map(|p| p.as_ref()).collect();
+        let (name, patterns) = patterns.split_first().ok_or_else(|| {
+            io::Error::new(io::ErrorKind::Other, "LIST needs at least one pattern")
+        })?;
+
+        let mut required = Vec::new();
+        if !patterns.is_empty() || !selection.is_empty() || !returns.is_empty() {
+            required.push("LIST-EXTENDED");
+        }
+        if selection.contains(&commands::ListSelection::SpecialUse)
+            || returns.contains(&commands::ListReturn::SpecialUse)
+        {
+            required.push("SPECIAL-USE");
+        }
+        if returns
+            .iter()
+            .any(|r| matches!(r, commands::ListReturn::Status(_)))
+        {
+            required.push("LIST-STATUS");
+        }
+        if let Some(missing) = required.into_iter().find(|c| !self.HasCapability(c)) {
+            return Err(io::Error::new(
+                io::ErrorKind::Other,
+                format!("server does not support {}", missing),
+            ));
+        }
+
+        let listed = self
             .execute(
                 commands::List {
+                    Reference: &reference,
                     Mailbox: name,
-                    Reference: reference,
-                    Subscribed: false,
+                    Patterns: patterns,
+                    Selection: selection,
+                    Return: returns,
+                    ..Default::default()
+                },
+                responses::List {
+                    Utf8: self.IsEnabled("UTF8=ACCEPT"),
+                    ..Default::default()
                 },
-                responses::List::default(),
             )
-            .await?)
+            .await?;
+
+        if everything && listed.1.IsOk() {
+            self.Mailboxes.Replace(prefix, &listed.0);
+        } else {
+            self.Mailboxes.Merge(&listed.0);
+        }
+        Ok(listed)
+    }
+
+    /// Lists the subscribed mailboxes matching `name`.
+    pub async fn Lsub(
+        &mut self,
+        reference: &'a str,
+        name: &'a str,
+    ) -> io::Result<(responses::List<'a>, Resp<'a>)> {
+        let (reference, encoded) = (self.mailboxName(reference), self.mailboxName(name));
+        let listed = self
+            .execute(
+                commands::List {
+                    Mailbox: &encoded,
+                    Reference: &reference,
+                    Subscribed: true,
+                    ..Default::default()
+                },
+                responses::List {
+                    Utf8: self.IsEnabled("UTF8=ACCEPT"),
+                    ..Default::default()
+                },
+            )
+            .await?;
+
+        self.Mailboxes.MergeSubscribed(&listed.0);
+
+        Ok(listed)
+    }
+
+    pub async fn Create(&mut self, name: &str) -> io::Result<Resp<'a>> {
+        self.CreateSpecialUse(name, &[]).await
+    }
+
+    /// Creates a mailbox with the given special-use roles, e.g.
+    /// `MailboxAttribute::Sent` (RFC 6154 CREATE-SPECIAL-USE).
+    pub async fn CreateSpecialUse(
+        &mut self,
+        name: &str,
+        roles: &[MailboxAttribute],
+    ) -> io::Result<Resp<'a>> {
+        if !roles.is_empty() && !self.HasCapability("CREATE-SPECIAL-USE") {
+            return Err(io::Error::new(
+                io::ErrorKind::Other,
+                "server does not support CREATE-SPECIAL-USE",
+            ));
+        }
+
+        let encoded = self.mailboxName(name);
+        let (_, resp) = self
+            .execute(
+                commands::Create {
+                    Mailbox: &encoded,
+                    SpecialUse: roles,
+                },
+                responses::None,
+            )
+            .await?;
+
+        if resp.IsOk() {
+            self.Mailboxes.Created(name, roles);
+        }
+
+        Ok(resp)
+    }
+
+    /// Finds the mailbox with a special-use role, such as the Sent or Trash
+    /// folder. Servers without SPECIAL-USE are searched for the names
+    /// commonly used for the role.
+    pub async fn FindSpecialUse(
+        &mut self,
+        role: &MailboxAttribute,
+    ) -> io::Result<Option<MailboxInfo<'a>>> {
+        if let Some(mbox) = self.Mailboxes.FindSpecialUse(role) {
+            return Ok(Some(mbox.clone()));
+        }
+
+        let (listed, resp) = if self.HasCapability("SPECIAL-USE") {
+            self.ListExtended("", &["*"], &[], &[commands::ListReturn::SpecialUse])
+                .await?
+        } else {
+            self.ListExtended("", &["*"], &[], &[]).await?
+        };
+        resp.Check()?;
+
+        Ok(listed.FindSpecialUse(role).cloned())
+    }
+
+    pub async fn Delete(&mut self, name: &str) -> io::Result<Resp<'a>> {
+        let encoded = self.mailboxName(name);
+        let (_, resp) = self
+            .execute(commands::Delete { Mailbox: &encoded }, responses::None)
+            .await?;
+
+        if resp.IsOk() {
+            self.Mailboxes.Deleted(name);
+        }
+
+        Ok(resp)
+    }
+
+    /// Renames a mailbox and all the mailboxes under it.
+    pub async fn Rename(&mut self, existing: &str, new: &str) -> io::Result<Resp<'a>> {
+        let (encodedExisting, encodedNew) = (self.mailboxName(existing), self.mailboxName(new));
+        let (_, resp) = self
+            .execute(
+                commands::Rename {
+                    Existing: &encodedExisting,
+                    New: &encodedNew,
+                },
+                responses::None,
+            )
+            .await?;
+
+        if resp.IsOk() {
+            self.Mailboxes.Renamed(existing, new);
+        }
+
+        Ok(resp)
+    }
+
+    pub async fn Subscribe(&mut self, name: &str) -> io::Result<Resp<'a>> {
+        self.setSubscribed(name, true).await
+    }
+
+    pub async fn Unsubscribe(&mut self, name: &str) -> io::Result<Resp<'a>> {
+        self.setSubscribed(name, false).await
     }
 
-    pub async fn execute<'e, C, H>(&mut self, cmdr: C, mut h: H) -> io::Result<(H, Resp<'e>)>
+    async fn setSubscribed(&mut self, name: &str, subscribed: bool) -> io::Result<Resp<'a>> {
+        let encoded = self.mailboxName(name);
+        let (_, resp) = self
+            .execute(
+                commands::Subscribe {
+                    Mailbox: &encoded,
+                    Unsubscribe: !subscribed,
+                },
+                responses::None,
+            )
+            .await?;
+
+        if resp.IsOk() {
+            self.Mailboxes.SetSubscribed(name, subscribed);
+        }
+
+        Ok(resp)
+    }
+
+    pub async fn execute<'e, C, H>(&mut self, cmdr: C, h: H) -> io::Result<(H, Resp<'e>)>
+    where
+        H: Handler<'e>,
+        C: Commander,
+    {
+        self.executeWith(cmdr, h, &mut None).await
+    }
+
+    /// Like `execute`, with `stream` supplying the data of a
+    /// `Literal::Stream` argument. `stream` is taken once its data is sent,
+    /// so it is still set if the server rejected the command before that.
+    pub async fn executeWith<'e, C, H>(
+        &mut self,
+        cmdr: C,
+        mut h: H,
+        stream: &mut Option<&mut (dyn AsyncRead + Unpin + Send)>,
+    ) -> io::Result<(H, Resp<'e>)>
     where
         H: Handler<'e>,
         C: Commander,
     {
         let cmd = cmdr.Command();
-        self.Writer.write(cmd.as_str().as_bytes()).await?;
+        // The first untagged response that couldn't be handled. The rest
+        // are still read up to the tagged one, so the next command doesn't
+        // get them.
+        let mut failed = None;
+        let parts = if self.IsEnabled("UTF8=ACCEPT") {
+            cmd.Utf8Parts()
+        } else {
+            cmd.Parts()
+        };
+
+        for part in parts {
+            self.Writer.write_all(part.Text.as_bytes()).await?;
+
+            let literal = match part.Literal {
+                Some(literal) => literal,
+                None => continue,
+            };
+
+            let n = literal.Len();
+            let nonSync =
+                self.HasCapability("LITERAL+") || (self.HasCapability("LITERAL-") && n <= 4096);
+
+            if nonSync {
+                self.Writer
+                    .write_all(format!("{{{}+}}\r\n", n).as_bytes())
+                    .await?;
+            } else {
+                self.Writer
+                    .write_all(format!("{{{}}}\r\n", n).as_bytes())
+                    .await?;
+                self.Writer.flush().await?;
+
+                // Wait for the server to accept the literal
+                loop {
+                    let mut resp = self.Reader.ReadResp().await?;
+                    match &resp {
+                        Resp::ContReq(_) => break,
+                        Resp::StatResp(StatusResp { Tag: tag, .. }) if tag == &cmd.Tag => {
+                            if let Err(err) = self.ApplyUpdate(&resp).await {
+                                failed.get_or_insert(err);
+                            }
+                            return match failed {
+                                Some(err) => Err(err),
+                                None => Ok((h, resp)),
+                            };
+                        }
+                        _ => {}
+                    }
+
+                    self.dispatch(&mut h, &mut resp, &mut failed).await;
+                }
+            }
+
+            match literal {
+                commands::Literal::Bytes(b) => self.Writer.write_all(b).await?,
+                commands::Literal::Stream(_) => {
+                    let reader = stream.take().ok_or_else(|| {
+                        io::Error::new(io::ErrorKind::Other, "no stream for literal")
+                    })?;
+
+                    let copied = futures_lite::io::copy(reader.take(n), &mut self.Writer).await?;
+                    if copied != n {
+                        return Err(io::Error::new(
+                            io::ErrorKind::UnexpectedEof,
+                            "stream ended before the end of the literal",
+                        ));
+                    }
+                }
+            }
+        }
+        self.Writer.flush().await?;
 
         let mut r = Resp::StatResp(Default::default());
         while let Ok(ref mut resp) = self.Reader.ReadResp().await {
             if let Resp::StatResp(StatusResp { Tag: tag, .. }) = resp {
                 if tag == &cmd.Tag {
+                    if let Err(err) = self.ApplyUpdate(resp).await {
+                        failed.get_or_insert(err);
+                    }
                     r = resp.to_owned();
                     break;
                 }
             }
 
-            h.Handle(resp).await?;
+            self.dispatch(&mut h, resp, &mut failed).await;
+        }
+
+        match failed {
+            Some(err) => Err(err),
+            None => Ok((h, r)),
+        }
+    }
+
+    /// Applies an untagged response and passes it to the handler. Errors
+    /// are kept in `failed` instead of returned.
+    async fn dispatch<'e, H: Handler<'e>>(
+        &mut self,
+        h: &mut H,
+        resp: &mut Resp<'e>,
+        failed: &mut Option<io::Error>,
+    ) {
+        if let Err(err) = self.ApplyUpdate(resp).await {
+            failed.get_or_insert(err);
+        }
+        if let Err(err) = h.Handle(resp).await {
+            failed.get_or_insert(err);
         }
+    }
 
-        Ok((h, r))
+    /// A mailbox name as sent to the server: in modified UTF-7, or as is
+    /// once UTF8=ACCEPT is enabled.
+    fn mailboxName<'n>(&self, name: &'n str) -> Cow<'n, str> {
+        if self.IsEnabled("UTF8=ACCEPT") {
+            return Cow::Borrowed(name);
+        }
+
+        match utf7::Encode(name) {
+            encoded if encoded == name => Cow::Borrowed(name),
+            encoded => Cow::Owned(encoded),
+        }
+    }
+
+    /// Sends the client's identification, such as
+    /// `("name", Some("my-client"))`, and stores the server's in `ServerId`.
+    /// Some servers refuse to SELECT before ID was sent.
+    pub async fn Id(
+        &mut self,
+        params: Option<&[(&str, Option<&str>)]>,
+    ) -> io::Result<(responses::Id, Resp<'a>)> {
+        if !self.HasCapability("ID") {
+            return Err(io::Error::new(
+                io::ErrorKind::Other,
+                "server does not support ID",
+            ));
+        }
+
+        let id = self
+            .execute(commands::Id { Params: params }, responses::Id::default())
+            .await?;
+
+        self.ServerId = id.0.Params.clone();
+        Ok(id)
+    }
+
+    /// Asks for the usage and limits of a quota root (RFC 9208).
+    pub async fn GetQuota(&mut self, root: &str) -> io::Result<(responses::Quota<'a>, Resp<'a>)> {
+        self.requireQuota()?;
+        self.execute(
+            commands::GetQuota { Root: root },
+            responses::Quota {
+                Utf8: self.IsEnabled("UTF8=ACCEPT"),
+                ..Default::default()
+            },
+        )
+        .await
+    }
+
+    /// Asks for the quota roots of a mailbox along with their usage and
+    /// limits (RFC 9208).
+    pub async fn GetQuotaRoot(
+        &mut self,
+        mailbox: &str,
+    ) -> io::Result<(responses::Quota<'a>, Resp<'a>)> {
+        self.requireQuota()?;
+        let encoded = self.mailboxName(mailbox);
+        self.execute(
+            commands::GetQuotaRoot { Mailbox: &encoded },
+            responses::Quota {
+                Utf8: self.IsEnabled("UTF8=ACCEPT"),
+                ..Default::default()
+            },
+        )
+        .await
+    }
+
+    /// Sets the limits of a quota root (RFC 9208), usually only allowed to
+    /// administrators.
+    pub async fn SetQuota(
+        &mut self,
+        root: &str,
+        limits: &[(commands::QuotaResource, u64)],
+    ) -> io::Result<(responses::Quota<'a>, Resp<'a>)> {
+        self.requireQuota()?;
+        self.execute(
+            commands::SetQuota {
+                Root: root,
+                Limits: limits,
+            },
+            responses::Quota {
+                Utf8: self.IsEnabled("UTF8=ACCEPT"),
+                ..Default::default()
+            },
+        )
+        .await
+    }
+
+    fn requireQuota(&self) -> io::Result<()> {
+        if !self.HasCapability("QUOTA") {
+            return Err(io::Error::new(
+                io::ErrorKind::Other,
+                "server does not support QUOTA",
+            ));
+        }
+
+        Ok(())
+    }
+
+    /// Asks for the access control list of a mailbox (RFC 4314).
+    pub async fn GetAcl(&mut self, mailbox: &str) -> io::Result<(responses::Acl<'a>, Resp<'a>)> {
+        self.requireAcl()?;
+        let encoded = self.mailboxName(mailbox);
+        self.execute(
+            commands::GetAcl { Mailbox: &encoded },
+            responses::Acl {
+                Utf8: self.IsEnabled("UTF8=ACCEPT"),
+                ..Default::default()
+            },
+        )
+        .await
+    }
+
+    /// Grants, adds or removes rights of `identifier` on a mailbox.
+    pub async fn SetAcl(
+        &mut self,
+        mailbox: &str,
+        identifier: &str,
+        mode: StoreMode,
+        rights: Rights,
+    ) -> io::Result<Resp<'a>> {
+        self.requireAcl()?;
+        let encoded = self.mailboxName(mailbox);
+        Ok(self
+            .execute(
+                commands::SetAcl {
+                    Mailbox: &encoded,
+                    Identifier: identifier,
+                    Mode: mode,
+                    Rights: rights,
+                },
+                responses::None,
+            )
+            .await?
+            .1)
+    }
+
+    pub async fn DeleteAcl(&mut self, mailbox: &str, identifier: &str) -> io::Result<Resp<'a>> {
+        self.requireAcl()?;
+        let encoded = self.mailboxName(mailbox);
+        Ok(self
+            .execute(
+                commands::DeleteAcl {
+                    Mailbox: &encoded,
+                    Identifier: identifier,
+                },
+                responses::None,
+            )
+            .await?
+            .1)
+    }
+
+    pub async fn ListRights(
+        &mut self,
+        mailbox: &str,
+        identifier: &str,
+    ) -> io::Result<(responses::Acl<'a>, Resp<'a>)> {
+        self.requireAcl()?;
+        let encoded = self.mailboxName(mailbox);
+        self.execute(
+            commands::ListRights {
+                Mailbox: &encoded,
+                Identifier: identifier,
+            },
+            responses::Acl {
+                Utf8: self.IsEnabled("UTF8=ACCEPT"),
+                ..Default::default()
+            },
+        )
+        .await
+    }
+
+    pub async fn MyRights(&mut self, mailbox: &str) -> io::Result<(responses::Acl<'a>, Resp<'a>)> {
+        self.requireAcl()?;
+        let encoded = self.mailboxName(mailbox);
+        self.execute(
+            commands::MyRights { Mailbox: &encoded },
+            responses::Acl {
+                Utf8: self.IsEnabled("UTF8=ACCEPT"),
+                ..Default::default()
+            },
+        )
+        .await
+    }
+
+    fn requireAcl(&self) -> io::Result<()> {
+        if !self.HasCapability("ACL") {
+            return Err(io::Error::new(
+                io::ErrorKind::Other,
+                "server does not support ACL",
+            ));
+        }
+
+        Ok(())
+    }
+
+    /// Reads annotations of a mailbox, or of the server with an empty
+    /// `mailbox` (RFC 5464). Values left out because of `maxSize` are
+    /// reported by `StatusResp::LongEntries`.
+    pub async fn GetMetadata(
+        &mut self,
+        mailbox: &str,
+        entries: &[&str],
+        maxSize: Option<u32>,
+        depth: commands::MetadataDepth,
+    ) -> io::Result<(responses::Metadata<'a>, Resp<'a>)> {
+        self.requireMetadata(mailbox)?;
+        let encoded = self.mailboxName(mailbox);
+        self.execute(
+            commands::GetMetadata {
+                Mailbox: &encoded,
+                Entries: entries,
+                MaxSize: maxSize,
+                Depth: depth,
+            },
+            responses::Metadata {
+                Utf8: self.IsEnabled("UTF8=ACCEPT"),
+                ..Default::default()
+            },
+        )
+        .await
+    }
+
+    /// Sets annotations of a mailbox or of the server, removing the ones
+    /// with a `None` value (RFC 5464). Failures are reported by `Check` as
+    /// `ErrorCode::MetadataTooMany`, `MetadataMaxSize` or `MetadataNoPrivate`.
+    pub async fn SetMetadata(
+        &mut self,
+        mailbox: &str,
+        entries: &[(&str, Option<&[u8]>)],
+    ) -> io::Result<Resp<'a>> {
+        self.requireMetadata(mailbox)?;
+        let encoded = self.mailboxName(mailbox);
+        Ok(self
+            .execute(
+                commands::SetMetadata {
+                    Mailbox: &encoded,
+                    Entries: entries,
+                },
+                responses::None,
+            )
+            .await?
+            .1)
+    }
+
+    /// METADATA covers mailbox and server annotations, METADATA-SERVER only
+    /// the latter.
+    fn requireMetadata(&self, mailbox: &str) -> io::Result<()> {
+        if self.HasCapability("METADATA")
+            || (mailbox.is_empty() && self.HasCapability("METADATA-SERVER"))
+        {
+            return Ok(());
+        }
+
+        Err(io::Error::new(
+            io::ErrorKind::Other,
+            "server does not support METADATA",
+        ))
+    }
+
+    /// Returns the messages matching `search`, by UID with `uid`. Searching
+    /// by MODSEQ requires CONDSTORE, and the result then carries the
+    /// highest mod-sequence of the matches.
+    pub async fn Search(
+        &mut self,
+        search: &commands::SearchKey<'_>,
+        uid: bool,
+    ) -> io::Result<(responses::Search<'a>, Resp<'a>)> {
+        if search.UsesModSeq() {
+            self.requireCondStore()?;
+        }
+
+        self.execute(
+            commands::Search {
+                Search: search,
+                Uid: uid,
+            },
+            responses::Search::default(),
+        )
+        .await
+    }
+
+    /// Returns the messages matching `search` ordered by `criteria`, by UID
+    /// with `uid` (RFC 5256).
+    pub async fn Sort(
+        &mut self,
+        criteria: &[commands::SortCriterion],
+        search: &commands::SearchKey<'_>,
+        uid: bool,
+    ) -> io::Result<(responses::Sort<'a>, Resp<'a>)> {
+        let display = criteria.iter().any(|c| {
+            matches!(
+                c.Key,
+                commands::SortKey::DisplayFrom | commands::SortKey::DisplayTo
+            )
+        });
+        let required = if display { "SORT=DISPLAY" } else { "SORT" };
+        if !self.HasCapability(required) {
+            return Err(io::Error::new(
+                io::ErrorKind::Other,
+                format!("server does not support {}", required),
+            ));
+        }
+
+        self.execute(
+            commands::Sort {
+                Criteria: criteria,
+                Search: search,
+                Uid: uid,
+            },
+            responses::Sort::default(),
+        )
+        .await
+    }
+
+    /// The THREAD algorithms the server advertises, best first.
+    pub fn ThreadAlgorithms(&self) -> Vec<commands::ThreadAlgorithm> {
+        let mut algorithms: Vec<commands::ThreadAlgorithm> = self
+            .capabilities
+            .iter()
+            .filter_map(TY::AsStr)
+            .filter_map(|c| {
+                let (name, value) = c.split_once('=')?;
+                if !name.eq_ignore_ascii_case("THREAD") {
+                    return None;
+                }
+                value.parse().ok()
+            })
+            .collect();
+        algorithms.sort_unstable_by(|a, b| b.cmp(a));
+        algorithms.dedup();
+        algorithms
+    }
+
+    /// Groups the messages matching `search` into threads (RFC 5256), by
+    /// UID with `uid`. Without an `algorithm`, the best one the server
+    /// advertises is used.
+    pub async fn Thread(
+        &mut self,
+        algorithm: Option<commands::ThreadAlgorithm>,
+        search: &commands::SearchKey<'_>,
+        uid: bool,
+    ) -> io::Result<(responses::Thread, Resp<'a>)> {
+        let algorithms = self.ThreadAlgorithms();
+        let algorithm = match algorithm {
+            Some(algorithm) if algorithms.contains(&algorithm) => algorithm,
+            Some(algorithm) => {
+                return Err(io::Error::new(
+                    io::ErrorKind::Other,
+                    format!("server does not support THREAD={}", algorithm.Name()),
+                ))
+            }
+            None => *algorithms.first().ok_or_else(|| {
+                io::Error::new(io::ErrorKind::Other, "server does not support THREAD")
+            })?,
+        };
+
+        self.execute(
+            commands::Thread {
+                Algorithm: algorithm,
+                Search: search,
+                Uid: uid,
+            },
+            responses::Thread::default(),
+        )
+        .await
+    }
+
+    /// Threads the messages of `seqSet` with the REFERENCES algorithm, by
+    /// UID with `uid`. Servers without THREAD=REFERENCES, such as Gmail, are
+    /// asked for the envelopes of the messages and the threads are built
+    /// here.
+    pub async fn ThreadMessages(
+        &mut self,
+        seqSet: &SequenceSet,
+        uid: bool,
+    ) -> io::Result<Vec<responses::ThreadNode>> {
+        let algorithm = commands::ThreadAlgorithm::References;
+        let search = if uid {
+            commands::SearchKey::Uid(seqSet)
+        } else {
+            commands::SearchKey::SeqSet(seqSet)
+        };
+        if self.ThreadAlgorithms().contains(&algorithm) {
+            let (thread, resp) = self.Thread(Some(algorithm), &search, uid).await?;
+            resp.Check()?;
+            return Ok(thread.Threads);
+        }
+
+        let messages = self
+            .fetchMatching(
+                &search,
+                uid,
+                &[
+                    commands::FetchItem::Uid,
+                    commands::FetchItem::Envelope,
+                    commands::FetchItem::HeaderFields(&["REFERENCES"]),
+                ],
+            )
+            .await?;
+
+        Ok(threading::References(&messages, uid))
+    }
+
+    /// Orders the messages of `seqSet` by `criteria` and returns their
+    /// sequence numbers, or UIDs with `uid`. Servers without SORT are asked
+    /// for the data of the messages and the messages are sorted here.
+    pub async fn SortMessages(
+        &mut self,
+        criteria: &[commands::SortCriterion],
+        seqSet: &SequenceSet,
+        uid: bool,
+    ) -> io::Result<Vec<u32>> {
+        let display = criteria.iter().any(|c| {
+            matches!(
+                c.Key,
+                commands::SortKey::DisplayFrom | commands::SortKey::DisplayTo
+            )
+        });
+        let search = if uid {
+            commands::SearchKey::Uid(seqSet)
+        } else {
+            commands::SearchKey::SeqSet(seqSet)
+        };
+        if self.HasCapability(if display { "SORT=DISPLAY" } else { "SORT" }) {
+            let (sorted, resp) = self.Sort(criteria, &search, uid).await?;
+            resp.Check()?;
+            return Ok(sorted.Ids.into_owned());
+        }
+
+        let messages = self
+            .fetchMatching(
+                &search,
+                uid,
+                &[
+                    commands::FetchItem::Uid,
+                    commands::FetchItem::Flags,
+                    commands::FetchItem::Envelope,
+                    commands::FetchItem::InternalDate,
+                    commands::FetchItem::Size,
+                ],
+            )
+            .await?;
+
+        Ok(sorting::Sort(&messages, criteria, uid))
+    }
+
+    /// Fetches `items` for the messages matching `search`, for doing what
+    /// SORT and THREAD do on servers without them. The matches are searched
+    /// first, then FETCHed `fetchBatch` at a time.
+    async fn fetchMatching(
+        &mut self,
+        search: &commands::SearchKey<'_>,
+        uid: bool,
+        items: &[commands::FetchItem<'_>],
+    ) -> io::Result<Vec<Message<'a>>> {
+        match &self.Mailbox {
+            // `*` has nothing to stand for in an empty mailbox
+            Some(mbox) if mbox.Messages == 0 => return Ok(Vec::new()),
+            Some(_) => {}
+            None => return Err(io::Error::new(io::ErrorKind::Other, "no mailbox selected")),
+        }
+
+        // A sparse set of UIDs can span far more ids than there are messages
+        let (found, resp) = self.Search(search, uid).await?;
+        resp.Check()?;
+        let batches = found
+            .Ids
+            .chunks(fetchBatch as usize)
+            .map(|ids| SequenceSet::FromIds(ids.iter().copied()));
+
+        let mut messages = Vec::new();
+        for batch in batches {
+            let (fetched, resp) = self.Fetch(&batch, uid, items).await?;
+            resp.Check()?;
+            messages.extend(fetched.Messages.into_owned());
+        }
+
+        Ok(messages)
+    }
+
+    /// Asks for the personal, other users' and shared namespaces
+    /// (RFC 2342), to find the prefixes of the mailboxes in each.
+    pub async fn Namespace(&mut self) -> io::Result<(responses::Namespace<'a>, Resp<'a>)> {
+        if !self.HasCapability("NAMESPACE") && !self.HasCapability("IMAP4rev2") {
+            return Err(io::Error::new(
+                io::ErrorKind::Other,
+                "server does not support NAMESPACE",
+            ));
+        }
+
+        self.execute(
+            commands::Namespace,
+            responses::Namespace {
+                Utf8: self.IsEnabled("UTF8=ACCEPT"),
+                ..Default::default()
+            },
+        )
+        .await
+    }
+
+    /// Turns on extensions such as CONDSTORE or UTF8=ACCEPT (RFC 5161).
+    /// Returns the ones the server enabled, which are also added to
+    /// `Enabled`.
+    pub async fn Enable(
+        &mut self,
+        capabilities: &[&str],
+    ) -> io::Result<(responses::Enable<'a>, Resp<'a>)> {
+        if !self.HasCapability("ENABLE") && !self.HasCapability("IMAP4rev2") {
+            return Err(io::Error::new(
+                io::ErrorKind::Other,
+                "server does not support ENABLE",
+            ));
+        }
+
+        self.execute(
+            commands::Enable {
+                Capabilities: capabilities,
+            },
+            responses::Enable::default(),
+        )
+        .await
+    }
+
+    pub fn IsEnabled(&self, name: &str) -> bool {
+        self.Enabled.iter().any(|c| c.eq_ignore_ascii_case(name))
+    }
+
+    pub fn HasCapability(&self, name: &str) -> bool {
+        self.capabilities
+            .iter()
+            .filter_map(TY::AsStr)
+            .any(|c| c.eq_ignore_ascii_case(name))
+    }
+
+    /// Applies the responses that change the connection or selected mailbox
+    /// state, whichever command they were sent for.
+    pub(crate) async fn ApplyUpdate(&mut self, resp: &Resp<'_>) -> io::Result<()> {
+        if let Some(msg) = fetch::ParseFetch(resp).await? {
+            if let (Some(mbox), Some(modSeq)) = (self.Mailbox.as_mut(), msg.ModSeq) {
+                if mbox.HighestModSeq.map_or(false, |highest| modSeq > highest) {
+                    mbox.HighestModSeq = Some(modSeq);
+                }
+            }
+
+            self.Messages
+                .entry(msg.SeqNum)
+                .or_insert_with(Default::default)
+                .Update(msg);
+            return Ok(());
+        }
+
+        // VANISHED replaces EXPUNGE once QRESYNC is enabled. EARLIER ones
+        // are about a previous session and change nothing here.
+        if let Some((uids, earlier)) = resync::ParseVanished(resp).await? {
+            if !earlier {
+                self.Vanished(&uids)?;
+            }
+            return Ok(());
+        }
+
+        match resp {
+            Resp::StatResp(StatusResp {
+                Code, Arguments, ..
+            }) if Code == "CAPABILITY" => {
+                self.capabilities = Arguments.iter().cloned().map(TY::IntoOwned).collect();
+            }
+            Resp::StatResp(status) if status.Code == "HIGHESTMODSEQ" => {
+                if let Some(mbox) = self.Mailbox.as_mut() {
+                    mbox.HighestModSeq = status.Arguments.get(0).and_then(TY::Parse);
+                }
+            }
+            Resp::DataResp(resp) => {
+                let (name, fields) = response::ParseNamedResp(resp).await?;
+                match name.as_ref() {
+                    "CAPABILITY" => {
+                        self.capabilities = fields.iter().cloned().map(TY::IntoOwned).collect();
+                    }
+                    "ENABLED" => {
+                        for capability in fields.iter().filter_map(TY::AsStr) {
+                            if !self.IsEnabled(capability) {
+                                self.Enabled.push(capability.to_string());
+                            }
+                        }
+                    }
+                    "EXISTS" => {
+                        if let Some(mbox) = self.Mailbox.as_mut() {
+                            mbox.Messages = fields.get(0).and_then(TY::Parse).unwrap_or_default();
+                        }
+                    }
+                    "EXPUNGE" => {
+                        if let Some(seqNum) = fields.get(0).and_then(TY::Parse) {
+                            self.Expunged(seqNum);
+                        }
+                    }
+                    _ => {}
+                }
+            }
+            _ => {}
+        }
+
+        Ok(())
+    }
+
+    /// Removes the messages with the given UIDs from the selected mailbox
+    /// state. If one of them is not cached its sequence number is unknown,
+    /// and so are the new numbers of the messages after it, so the cache is
+    /// dropped instead.
+    fn Vanished(&mut self, uids: &SequenceSet) -> io::Result<()> {
+        let seqNums: BTreeMap<u32, u32> = self
+            .Messages
+            .values()
+            .filter_map(|m| Some((m.Uid?, m.SeqNum)))
+            .collect();
+
+        let mut known = Vec::new();
+        let mut unknown = 0;
+        for uid in uids.Ids()? {
+            match seqNums.get(&uid) {
+                Some(&seqNum) => known.push(seqNum),
+                None => unknown += 1,
+            }
+        }
+
+        if unknown > 0 {
+            self.Messages.clear();
+            if let Some(mbox) = self.Mailbox.as_mut() {
+                mbox.Messages = mbox.Messages.saturating_sub(known.len() + unknown);
+            }
+            return Ok(());
+        }
+
+        // Highest first, so the lower numbers still hold
+        known.sort_unstable();
+        for seqNum in known.into_iter().rev() {
+            self.Expunged(seqNum);
+        }
+        Ok(())
+    }
+
+    /// Removes a message from the selected mailbox state and renumbers the
+    /// messages after it.
+    fn Expunged(&mut self, seqNum: u32) {
+        self.Messages.remove(&seqNum);
+
+        let after = self.Messages.split_off(&seqNum);
+        for (n, mut msg) in after {
+            msg.SeqNum = n - 1;
+            self.Messages.insert(n - 1, msg);
+        }
+
+        if let Some(mbox) = self.Mailbox.as_mut() {
+            mbox.Messages = mbox.Messages.saturating_sub(1);
+        }
     }
 }
 
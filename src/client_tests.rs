@@ -0,0 +1,60 @@
+use crate::{
+    responses::{select::MailboxStatus, Message},
+    Client, Reader,
+};
+use futures_lite::{io::Cursor, AsyncReadExt};
+
+fn selected<'a>(uids: &[(u32, u32)], messages: usize) -> Client<'a, Cursor<Vec<u8>>> {
+    let addr = "127.0.0.1:143".parse().unwrap();
+    let mut c = Client::New(Cursor::new(Vec::new()), addr, addr);
+    c.Mailbox = Some(MailboxStatus {
+        Messages: messages,
+        ..Default::default()
+    });
+    for &(seqNum, uid) in uids {
+        c.Messages.insert(
+            seqNum,
+            Message {
+                SeqNum: seqNum,
+                Uid: Some(uid),
+                ..Default::default()
+            },
+        );
+    }
+    c
+}
+
+async fn apply(c: &mut Client<'_, Cursor<Vec<u8>>>, input: &[u8]) {
+    let resp = Reader::from(input.bytes()).ReadResp().await.unwrap();
+    c.ApplyUpdate(&resp).await.unwrap();
+}
+
+fn uids(c: &Client<'_, Cursor<Vec<u8>>>) -> Vec<(u32, Option<u32>)> {
+    c.Messages
+        .iter()
+        .map(|(&seqNum, m)| {
+            debug_assert!(m.SeqNum == seqNum);
+            (seqNum, m.Uid)
+        })
+        .collect()
+}
+
+#[test]
+fn TestApplyUpdate_Vanished() {
+    smol::block_on(async {
+        let mut c = selected(&[(1, 10), (2, 20), (3, 30), (4, 40), (5, 50)], 5);
+        apply(&mut c, b"* VANISHED 20,40\r\n").await;
+        debug_assert!(uids(&c) == [(1, Some(10)), (2, Some(30)), (3, Some(50))]);
+        debug_assert!(c.Mailbox.as_ref().unwrap().Messages == 3);
+
+        // EARLIER is about messages expunged before this session
+        apply(&mut c, b"* VANISHED (EARLIER) 10\r\n").await;
+        debug_assert!(uids(&c).len() == 3);
+
+        // Message 4 is not cached, the numbers after it can't be known
+        let mut c = selected(&[(1, 10), (2, 20), (3, 30), (5, 50)], 5);
+        apply(&mut c, b"* VANISHED 20,40\r\n").await;
+        debug_assert!(c.Messages.is_empty());
+        debug_assert!(c.Mailbox.as_ref().unwrap().Messages == 3);
+    })
+}
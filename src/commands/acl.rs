@@ -0,0 +1,98 @@
+use super::{Arg, Command, Commander, StoreMode};
+use crate::Rights;
+use std::borrow::Cow;
+
+fn mailboxCommand<'c>(name: &'static str, mailbox: &'c str, rest: &[Arg<'c>]) -> Command<'c> {
+    let mut args = Cow::<'c, [Arg<'c>]>::default();
+    args.to_mut().push(Arg::AString(mailbox));
+    args.to_mut().extend_from_slice(rest);
+
+    Command {
+        Tag: "a001".into(),
+        Name: name.into(),
+        Arguments: args,
+    }
+}
+
+/// Asks for the access control list of a mailbox (RFC 4314).
+#[derive(Debug, Default)]
+pub struct GetAcl<'a> {
+    pub Mailbox: &'a str,
+}
+
+impl<'a> Commander for GetAcl<'a> {
+    fn Command<'c>(&'c self) -> Command<'c> {
+        mailboxCommand("GETACL", self.Mailbox, &[])
+    }
+}
+
+/// Changes the rights of an identifier on a mailbox. `StoreMode::Add` and
+/// `StoreMode::Remove` send the rights with a `+` or `-` prefix.
+#[derive(Debug)]
+pub struct SetAcl<'a> {
+    pub Mailbox: &'a str,
+    pub Identifier: &'a str,
+    pub Mode: StoreMode,
+    pub Rights: Rights,
+}
+
+impl<'a> Commander for SetAcl<'a> {
+    fn Command<'c>(&'c self) -> Command<'c> {
+        let prefix = match self.Mode {
+            StoreMode::Replace => "",
+            StoreMode::Add => "+",
+            StoreMode::Remove => "-",
+        };
+        let rights = format!("{}{}", prefix, self.Rights);
+
+        mailboxCommand(
+            "SETACL",
+            self.Mailbox,
+            &[
+                Arg::AString(self.Identifier),
+                Arg::Raw(if rights.is_empty() {
+                    "\"\"".into()
+                } else {
+                    rights.into()
+                }),
+            ],
+        )
+    }
+}
+
+#[derive(Debug, Default)]
+pub struct DeleteAcl<'a> {
+    pub Mailbox: &'a str,
+    pub Identifier: &'a str,
+}
+
+impl<'a> Commander for DeleteAcl<'a> {
+    fn Command<'c>(&'c self) -> Command<'c> {
+        mailboxCommand("DELETEACL", self.Mailbox, &[Arg::AString(self.Identifier)])
+    }
+}
+
+/// Asks which rights can be granted to an identifier on a mailbox.
+#[derive(Debug, Default)]
+pub struct ListRights<'a> {
+    pub Mailbox: &'a str,
+    pub Identifier: &'a str,
+}
+
+impl<'a> Commander for ListRights<'a> {
+    fn Command<'c>(&'c self) -> Command<'c> {
+        mailboxCommand("LISTRIGHTS", self.Mailbox, &[Arg::AString(self.Identifier)])
+    }
+}
+
+/// Asks for the rights of the logged in user on a mailbox.
+#[derive(Debug, Default)]
+pub struct MyRights<'a> {
+    pub Mailbox: &'a str,
+}
+
+impl<'a> Commander for MyRights<'a> {
+    fn Command<'c>(&'c self) -> Command<'c> {
+        mailboxCommand("MYRIGHTS", self.Mailbox, &[])
+    }
+}
@@ -0,0 +1,150 @@
+use super::{Arg, Command, Commander, Literal};
+use crate::flag::{self, Flag};
+use std::borrow::Cow;
+
+#[derive(Debug)]
+pub struct Append<'a> {
+    pub Mailbox: &'a str,
+    pub Flags: &'a [Flag],
+    /// The internal date, as an RFC 3501 date-time such as
+    /// `17-Jul-1996 02:44:25 -0700`.
+    pub Date: Option<&'a str>,
+    pub Message: Literal<'a>,
+    /// Sends the message as `UTF8 (~{n})`, for messages with UTF-8 headers
+    /// once UTF8=ACCEPT is enabled (RFC 6855).
+    pub Utf8: bool,
+}
+
+impl<'a> Commander for Append<'a> {
+    fn Command<'c>(&'c self) -> Command<'c> {
+        let mut args = Cow::<'c, [Arg<'c>]>::default();
+        args.to_mut().push(Arg::AString(self.Mailbox));
+        let message = match &self.Message {
+            Literal::Bytes(b) => Literal::Bytes(Cow::Borrowed(b.as_ref())),
+            Literal::Stream(n) => Literal::Stream(*n),
+        };
+        pushMessage(&mut args, self.Flags, self.Date, message, self.Utf8);
+
+        Command {
+            Tag: "a001".into(),
+            Name: "APPEND".into(),
+            Arguments: args,
+        }
+    }
+}
+
+/// One message of a MULTIAPPEND command.
+#[derive(Debug)]
+pub struct AppendMessage<'a> {
+    pub Flags: &'a [Flag],
+    pub Date: Option<&'a str>,
+    pub Message: &'a [u8],
+}
+
+/// APPEND of several messages at once, defined in RFC 3502. Requires the
+/// MULTIAPPEND capability. Either all messages are appended or none is.
+#[derive(Debug)]
+pub struct MultiAppend<'a> {
+    pub Mailbox: &'a str,
+    pub Messages: &'a [AppendMessage<'a>],
+    /// See `Append::Utf8`.
+    pub Utf8: bool,
+}
+
+impl<'a> Commander for MultiAppend<'a> {
+    fn Command<'c>(&'c self) -> Command<'c> {
+        let mut args = Cow::<'c, [Arg<'c>]>::default();
+        args.to_mut().push(Arg::AString(self.Mailbox));
+
+        for msg in self.Messages.iter() {
+            let message = Literal::Bytes(msg.Message.into());
+            pushMessage(&mut args, msg.Flags, msg.Date, message, self.Utf8);
+        }
+
+        Command {
+            Tag: "a001".into(),
+            Name: "APPEND".into(),
+            Arguments: args,
+        }
+    }
+}
+
+fn pushMessage<'c>(
+    args: &mut Cow<'c, [Arg<'c>]>,
+    flags: &'c [Flag],
+    date: Option<&'c str>,
+    message: Literal<'c>,
+    utf8: bool,
+) {
+    pushOptions(args, flags, date);
+
+    if utf8 {
+        args.to_mut().push("UTF8".into());
+        args.to_mut()
+            .push(Arg::List(vec![Arg::Literal8(message)].into()));
+    } else {
+        args.to_mut().push(Arg::Literal(message));
+    }
+}
+
+fn pushOptions<'c>(args: &mut Cow<'c, [Arg<'c>]>, flags: &'c [Flag], date: Option<&'c str>) {
+    if !flags.is_empty() {
+        args.to_mut().push(flag::FormatFlags(flags).into());
+    }
+
+    if let Some(date) = date {
+        args.to_mut().push(format!("\"{}\"", date).into());
+    }
+}
+
+/// A part of a message assembled with CATENATE.
+#[derive(Debug)]
+pub enum CatenatePart<'a> {
+    /// An IMAP URL (RFC 5092) of a message or body part on the server.
+    Url(&'a str),
+    /// Data sent by the client.
+    Text(&'a [u8]),
+}
+
+/// APPEND of a message built by concatenating text and server-side parts,
+/// defined in RFC 4469. Requires the CATENATE capability.
+#[derive(Debug)]
+pub struct Catenate<'a> {
+    pub Mailbox: &'a str,
+    pub Flags: &'a [Flag],
+    pub Date: Option<&'a str>,
+    pub Parts: &'a [CatenatePart<'a>],
+}
+
+impl<'a> Commander for Catenate<'a> {
+    fn Command<'c>(&'c self) -> Command<'c> {
+        let mut args = Cow::<'c, [Arg<'c>]>::default();
+        args.to_mut().push(Arg::AString(self.Mailbox));
+        pushOptions(&mut args, self.Flags, self.Date);
+
+        let mut parts = Cow::<'c, [Arg<'c>]>::default();
+        for part in self.Parts.iter() {
+            match part {
+                CatenatePart::Url(url) => {
+                    parts.to_mut().push("URL".into());
+                    parts.to_mut().push(Arg::AString(url));
+                }
+                CatenatePart::Text(text) => {
+                    parts.to_mut().push("TEXT".into());
+                    parts
+                        .to_mut()
+                        .push(Arg::Literal(Literal::Bytes((*text).into())));
+                }
+            }
+        }
+
+        args.to_mut().push("CATENATE".into());
+        args.to_mut().push(Arg::List(parts));
+
+        Command {
+            Tag: "a001".into(),
+            Name: "APPEND".into(),
+            Arguments: args,
+        }
+    }
+}
@@ -0,0 +1,46 @@
+use super::{Arg, Command, Commander};
+use crate::SequenceSet;
+use std::borrow::Cow;
+
+#[derive(Debug)]
+pub struct Copy<'a> {
+    pub SeqSet: &'a SequenceSet,
+    pub Uid: bool,
+    pub Mailbox: &'a str,
+}
+
+impl<'a> Commander for Copy<'a> {
+    fn Command<'c>(&'c self) -> Command<'c> {
+        let mut args = Cow::<'c, [Arg<'c>]>::default();
+        args.to_mut().push(self.SeqSet.to_string().into());
+        args.to_mut().push(Arg::AString(self.Mailbox));
+
+        Command {
+            Tag: "a001".into(),
+            Name: if self.Uid { "UID COPY" } else { "COPY" }.into(),
+            Arguments: args,
+        }
+    }
+}
+
+/// MOVE command, defined in RFC 6851. Requires the MOVE capability.
+#[derive(Debug)]
+pub struct Move<'a> {
+    pub SeqSet: &'a SequenceSet,
+    pub Uid: bool,
+    pub Mailbox: &'a str,
+}
+
+impl<'a> Commander for Move<'a> {
+    fn Command<'c>(&'c self) -> Command<'c> {
+        let mut args = Cow::<'c, [Arg<'c>]>::default();
+        args.to_mut().push(self.SeqSet.to_string().into());
+        args.to_mut().push(Arg::AString(self.Mailbox));
+
+        Command {
+            Tag: "a001".into(),
+            Name: if self.Uid { "UID MOVE" } else { "MOVE" }.into(),
+            Arguments: args,
+        }
+    }
+}
@@ -0,0 +1,24 @@
+use super::{Arg, Command, Commander};
+use std::borrow::Cow;
+
+/// Turns on extensions that change how the server talks to the client,
+/// defined in RFC 5161. Requires the ENABLE capability.
+#[derive(Debug, Default)]
+pub struct Enable<'a> {
+    pub Capabilities: &'a [&'a str],
+}
+
+impl<'a> Commander for Enable<'a> {
+    fn Command<'c>(&'c self) -> Command<'c> {
+        let mut args = Cow::<'c, [Arg<'c>]>::default();
+        for capability in self.Capabilities.iter() {
+            args.to_mut().push((*capability).into());
+        }
+
+        Command {
+            Tag: "a001".into(),
+            Name: "ENABLE".into(),
+            Arguments: args,
+        }
+    }
+}
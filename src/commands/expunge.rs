@@ -0,0 +1,30 @@
+use super::{Arg, Command, Commander};
+use crate::SequenceSet;
+use std::borrow::Cow;
+
+/// EXPUNGE command. With `Uids` set, sends `UID EXPUNGE` (RFC 4315) which
+/// only removes the given messages. Requires the UIDPLUS capability.
+#[derive(Debug, Default)]
+pub struct Expunge<'a> {
+    pub Uids: Option<&'a SequenceSet>,
+}
+
+impl<'a> Commander for Expunge<'a> {
+    fn Command<'c>(&'c self) -> Command<'c> {
+        let mut args = Cow::<'c, [Arg<'c>]>::default();
+        if let Some(uids) = self.Uids {
+            args.to_mut().push(uids.to_string().into());
+        }
+
+        Command {
+            Tag: "a001".into(),
+            Name: if self.Uids.is_some() {
+                "UID EXPUNGE"
+            } else {
+                "EXPUNGE"
+            }
+            .into(),
+            Arguments: args,
+        }
+    }
+}
@@ -0,0 +1,72 @@
+use super::{Arg, Command, Commander};
+use crate::SequenceSet;
+use std::borrow::Cow;
+
+/// A message data item to FETCH.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum FetchItem<'a> {
+    Uid,
+    Flags,
+    Envelope,
+    InternalDate,
+    /// `RFC822.SIZE`.
+    Size,
+    /// The mod-sequence of the message (RFC 7162).
+    ModSeq,
+    /// Some header fields, fetched with BODY.PEEK so the message isn't
+    /// marked \Seen. Returned in `Message::Header`.
+    HeaderFields(&'a [&'a str]),
+}
+
+impl<'a> FetchItem<'a> {
+    pub fn Name(&self) -> String {
+        match self {
+            FetchItem::Uid => "UID".into(),
+            FetchItem::Flags => "FLAGS".into(),
+            FetchItem::Envelope => "ENVELOPE".into(),
+            FetchItem::InternalDate => "INTERNALDATE".into(),
+            FetchItem::Size => "RFC822.SIZE".into(),
+            FetchItem::ModSeq => "MODSEQ".into(),
+            FetchItem::HeaderFields(fields) => {
+                format!("BODY.PEEK[HEADER.FIELDS ({})]", fields.join(" "))
+            }
+        }
+    }
+}
+
+#[derive(Debug)]
+pub struct Fetch<'a> {
+    pub SeqSet: &'a SequenceSet,
+    pub Uid: bool,
+    pub Items: &'a [FetchItem<'a>],
+    /// Only fetches messages whose mod-sequence is higher (RFC 7162).
+    pub ChangedSince: Option<u64>,
+    /// With `ChangedSince` on a UID FETCH, also asks for the UIDs expunged
+    /// since then, in `* VANISHED (EARLIER)` (RFC 7162). QRESYNC must have
+    /// been enabled.
+    pub Vanished: bool,
+}
+
+impl<'a> Commander for Fetch<'a> {
+    fn Command<'c>(&'c self) -> Command<'c> {
+        let mut args = Cow::<'c, [Arg<'c>]>::default();
+        args.to_mut().push(self.SeqSet.to_string().into());
+        args.to_mut().push(Arg::List(
+            self.Items.iter().map(|i| i.Name().into()).collect(),
+        ));
+        if let Some(modSeq) = self.ChangedSince {
+            let mut modifiers: Vec<Arg<'c>> =
+                vec!["CHANGEDSINCE".into(), modSeq.to_string().into()];
+            if self.Vanished {
+                modifiers.push("VANISHED".into());
+            }
+            args.to_mut().push(Arg::List(modifiers.into()));
+        }
+
+        Command {
+            Tag: "a001".into(),
+            Name: if self.Uid { "UID FETCH" } else { "FETCH" }.into(),
+            Arguments: args,
+        }
+    }
+}
@@ -0,0 +1,33 @@
+use super::{Arg, Command, Commander};
+use std::borrow::Cow;
+
+/// Tells the server who the client is and asks who the server is, defined
+/// in RFC 2971. Requires the ID capability. `None` or no parameters send
+/// `ID NIL`.
+#[derive(Debug, Default)]
+pub struct Id<'a> {
+    /// Field names and values, a `None` value is sent as `NIL`.
+    pub Params: Option<&'a [(&'a str, Option<&'a str>)]>,
+}
+
+impl<'a> Commander for Id<'a> {
+    fn Command<'c>(&'c self) -> Command<'c> {
+        let mut args = Cow::<'c, [Arg<'c>]>::default();
+        match self.Params {
+            Some(params) if !params.is_empty() => {
+                let list = params
+                    .iter()
+                    .flat_map(|(k, v)| [Arg::String(k), v.map_or("NIL".into(), Arg::String)])
+                    .collect();
+                args.to_mut().push(Arg::List(list));
+            }
+            _ => args.to_mut().push("NIL".into()),
+        }
+
+        Command {
+            Tag: "a001".into(),
+            Name: "ID".into(),
+            Arguments: args,
+        }
+    }
+}
@@ -1,18 +1,93 @@
-use super::{Command, Commander};
+use super::{Arg, Command, Commander, StatusItem};
 use std::borrow::Cow;
 
+/// LIST selection option, defined in RFC 5258.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ListSelection {
+    Subscribed,
+    Remote,
+    RecursiveMatch,
+    /// Defined in RFC 6154, requires SPECIAL-USE.
+    SpecialUse,
+}
+
+/// LIST return option, defined in RFC 5258.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ListReturn<'a> {
+    Children,
+    Subscribed,
+    /// Defined in RFC 6154, requires SPECIAL-USE.
+    SpecialUse,
+    /// Defined in RFC 5819, requires LIST-STATUS.
+    Status(&'a [StatusItem]),
+}
+
 #[derive(Debug, Default)]
 pub struct List<'a> {
     pub Reference: &'a str,
     pub Mailbox: &'a str,
     pub Subscribed: bool,
+    /// More patterns to match along with `Mailbox`. Requires LIST-EXTENDED.
+    pub Patterns: &'a [&'a str],
+    /// Requires LIST-EXTENDED.
+    pub Selection: &'a [ListSelection],
+    /// Requires LIST-EXTENDED.
+    pub Return: &'a [ListReturn<'a>],
 }
 
 impl<'a> Commander for List<'a> {
     fn Command<'c>(&'c self) -> Command<'c> {
-        let mut args = Cow::<'c, [Cow<'c, str>]>::default();
-        args.to_mut().push(self.Reference.into());
-        args.to_mut().push(self.Mailbox.into());
+        let mut args = Cow::<'c, [Arg<'c>]>::default();
+
+        if !self.Selection.is_empty() {
+            args.to_mut().push(Arg::List(
+                self.Selection
+                    .iter()
+                    .map(|s| {
+                        match s {
+                            ListSelection::Subscribed => "SUBSCRIBED",
+                            ListSelection::Remote => "REMOTE",
+                            ListSelection::RecursiveMatch => "RECURSIVEMATCH",
+                            ListSelection::SpecialUse => "SPECIAL-USE",
+                        }
+                        .into()
+                    })
+                    .collect(),
+            ));
+        }
+
+        args.to_mut().push(Arg::AString(self.Reference));
+
+        if self.Patterns.is_empty() {
+            args.to_mut().push(Arg::ListMailbox(self.Mailbox));
+        } else {
+            args.to_mut().push(Arg::List(
+                std::iter::once(self.Mailbox)
+                    .chain(self.Patterns.iter().copied())
+                    .map(Arg::ListMailbox)
+                    .collect(),
+            ));
+        }
+
+        if !self.Return.is_empty() {
+            args.to_mut().push("RETURN".into());
+            args.to_mut().push(Arg::List(
+                self.Return
+                    .iter()
+                    .map(|r| {
+                        match r {
+                            ListReturn::Children => "CHILDREN",
+                            ListReturn::Subscribed => "SUBSCRIBED",
+                            ListReturn::SpecialUse => "SPECIAL-USE",
+                            ListReturn::Status(items) => {
+                                return format!("STATUS {}", StatusItem::FormatList(items)).into()
+                            }
+                        }
+                        .into()
+                    })
+                    .collect(),
+            ));
+        }
 
         Command {
             Tag: "a001".into(),
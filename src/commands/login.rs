@@ -1,4 +1,4 @@
-use super::{Command, Commander};
+use super::{Arg, Command, Commander};
 use std::borrow::Cow;
 
 #[derive(Debug, Default)]
@@ -9,7 +9,7 @@ pub struct Login<'a> {
 
 impl<'a> Commander for Login<'a> {
     fn Command<'c>(&'c self) -> Command<'c> {
-        let mut args: Cow<'c, [Cow<'c, str>]> = Default::default();
+        let mut args: Cow<'c, [Arg<'c>]> = Default::default();
         args.to_mut().push(self.UserName.into());
         args.to_mut().push(self.Password.into());
 
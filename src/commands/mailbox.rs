@@ -0,0 +1,94 @@
+use super::{Arg, Command, Commander};
+use crate::responses::MailboxAttribute;
+use std::borrow::Cow;
+
+#[derive(Debug, Default)]
+pub struct Create<'a> {
+    pub Mailbox: &'a str,
+    /// Special-use roles to give the new mailbox, sent as `(USE (...))`
+    /// (RFC 6154 CREATE-SPECIAL-USE).
+    pub SpecialUse: &'a [MailboxAttribute],
+}
+
+impl<'a> Commander for Create<'a> {
+    fn Command<'c>(&'c self) -> Command<'c> {
+        let mut args = Cow::<'c, [Arg<'c>]>::default();
+        args.to_mut().push(Arg::AString(self.Mailbox));
+
+        if !self.SpecialUse.is_empty() {
+            let roles = self.SpecialUse.iter().map(|a| a.to_string().into());
+            args.to_mut().push(Arg::List(
+                vec!["USE".into(), Arg::List(roles.collect())].into(),
+            ));
+        }
+
+        Command {
+            Tag: "a001".into(),
+            Name: "CREATE".into(),
+            Arguments: args,
+        }
+    }
+}
+
+#[derive(Debug, Default)]
+pub struct Delete<'a> {
+    pub Mailbox: &'a str,
+}
+
+impl<'a> Commander for Delete<'a> {
+    fn Command<'c>(&'c self) -> Command<'c> {
+        let mut args = Cow::<'c, [Arg<'c>]>::default();
+        args.to_mut().push(Arg::AString(self.Mailbox));
+
+        Command {
+            Tag: "a001".into(),
+            Name: "DELETE".into(),
+            Arguments: args,
+        }
+    }
+}
+
+#[derive(Debug, Default)]
+pub struct Rename<'a> {
+    pub Existing: &'a str,
+    pub New: &'a str,
+}
+
+impl<'a> Commander for Rename<'a> {
+    fn Command<'c>(&'c self) -> Command<'c> {
+        let mut args = Cow::<'c, [Arg<'c>]>::default();
+        args.to_mut().push(Arg::AString(self.Existing));
+        args.to_mut().push(Arg::AString(self.New));
+
+        Command {
+            Tag: "a001".into(),
+            Name: "RENAME".into(),
+            Arguments: args,
+        }
+    }
+}
+
+#[derive(Debug, Default)]
+pub struct Subscribe<'a> {
+    pub Mailbox: &'a str,
+    /// Sends UNSUBSCRIBE instead.
+    pub Unsubscribe: bool,
+}
+
+impl<'a> Commander for Subscribe<'a> {
+    fn Command<'c>(&'c self) -> Command<'c> {
+        let mut args = Cow::<'c, [Arg<'c>]>::default();
+        args.to_mut().push(Arg::AString(self.Mailbox));
+
+        Command {
+            Tag: "a001".into(),
+            Name: if self.Unsubscribe {
+                "UNSUBSCRIBE"
+            } else {
+                "SUBSCRIBE"
+            }
+            .into(),
+            Arguments: args,
+        }
+    }
+}
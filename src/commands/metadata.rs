@@ -0,0 +1,92 @@
+use super::{Arg, Command, Commander, Literal};
+use std::borrow::Cow;
+
+/// How deep below the requested entries GETMETADATA looks.
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+pub enum MetadataDepth {
+    /// Only the entries themselves.
+    #[default]
+    Zero,
+    /// The entries and their immediate children.
+    One,
+    /// The entries and everything below them.
+    Infinity,
+}
+
+/// Reads server (`Mailbox` empty) or mailbox annotations, defined in
+/// RFC 5464. Requires the METADATA or METADATA-SERVER capability.
+#[derive(Debug, Default)]
+pub struct GetMetadata<'a> {
+    pub Mailbox: &'a str,
+    /// Entry names such as `/private/comment`.
+    pub Entries: &'a [&'a str],
+    /// Leaves out values larger than this many octets, see
+    /// `StatusResp::LongEntries`.
+    pub MaxSize: Option<u32>,
+    pub Depth: MetadataDepth,
+}
+
+impl<'a> Commander for GetMetadata<'a> {
+    fn Command<'c>(&'c self) -> Command<'c> {
+        let mut args = Cow::<'c, [Arg<'c>]>::default();
+
+        let mut options = Vec::new();
+        if let Some(size) = self.MaxSize {
+            options.push("MAXSIZE".into());
+            options.push(size.to_string().into());
+        }
+        match self.Depth {
+            MetadataDepth::Zero => {}
+            MetadataDepth::One => options.extend(["DEPTH".into(), "1".into()]),
+            MetadataDepth::Infinity => options.extend(["DEPTH".into(), "infinity".into()]),
+        }
+        if !options.is_empty() {
+            args.to_mut().push(Arg::List(options.into()));
+        }
+
+        args.to_mut().push(Arg::AString(self.Mailbox));
+        args.to_mut().push(Arg::List(
+            self.Entries.iter().map(|e| Arg::AString(e)).collect(),
+        ));
+
+        Command {
+            Tag: "a001".into(),
+            Name: "GETMETADATA".into(),
+            Arguments: args,
+        }
+    }
+}
+
+/// Sets or, with a `None` value, removes annotations (RFC 5464).
+#[derive(Debug, Default)]
+pub struct SetMetadata<'a> {
+    pub Mailbox: &'a str,
+    pub Entries: &'a [(&'a str, Option<&'a [u8]>)],
+}
+
+impl<'a> Commander for SetMetadata<'a> {
+    fn Command<'c>(&'c self) -> Command<'c> {
+        let mut args = Cow::<'c, [Arg<'c>]>::default();
+        args.to_mut().push(Arg::AString(self.Mailbox));
+
+        let mut entries = Vec::new();
+        for (entry, value) in self.Entries.iter() {
+            entries.push(Arg::AString(entry));
+            entries.push(match value {
+                None => "NIL".into(),
+                Some(value) => match std::str::from_utf8(value) {
+                    Ok(text) if !text.contains('\0') => Arg::String(text),
+                    // Binary values need a literal8
+                    _ => Arg::Literal8(Literal::Bytes((*value).into())),
+                },
+            });
+        }
+        args.to_mut().push(Arg::List(entries.into()));
+
+        Command {
+            Tag: "a001".into(),
+            Name: "SETMETADATA".into(),
+            Arguments: args,
+        }
+    }
+}
@@ -1,4 +1,4 @@
-use std::{borrow::Cow, fmt::Write, str::FromStr};
+use std::borrow::Cow;
 
 use async_trait::async_trait;
 
@@ -6,7 +6,121 @@ use async_trait::async_trait;
 pub struct Command<'c> {
     pub Tag: Cow<'c, str>,
     pub Name: Cow<'c, str>,
-    pub Arguments: Cow<'c, [Cow<'c, str>]>,
+    pub Arguments: Cow<'c, [Arg<'c>]>,
+}
+
+/// A command argument.
+#[derive(Debug, Clone)]
+pub enum Arg<'c> {
+    /// Sent as is: atoms, numbers, quoted strings and pre-formatted lists.
+    Raw(Cow<'c, str>),
+    /// Sent as a parenthesized, space separated list.
+    List(Cow<'c, [Arg<'c>]>),
+    Literal(Literal<'c>),
+    /// Sent as a `~{n}` literal8, which may hold any byte (RFC 3516). Used for
+    /// messages appended with UTF8=ACCEPT.
+    Literal8(Literal<'c>),
+    /// A string with non-ASCII characters, in a `Literal::Bytes`. Sent as a
+    /// quoted string once UTF8=ACCEPT is enabled, as a literal otherwise.
+    Utf8(Literal<'c>),
+}
+
+impl<'c> Arg<'c> {
+    /// Formats a string as an atom when possible, otherwise as a quoted
+    /// string, or as a literal if it cannot be quoted.
+    pub fn AString(s: &'c str) -> Arg<'c> {
+        if s.bytes().any(|b| b == b'\r' || b == b'\n') {
+            return Arg::Literal(Literal::Bytes(s.as_bytes().into()));
+        }
+
+        if !s.is_ascii() {
+            return Arg::Utf8(Literal::Bytes(s.as_bytes().into()));
+        }
+
+        if !s.is_empty()
+            && s.bytes()
+                .all(|b| !b.is_ascii_control() && !b"(){ %*\"\\".contains(&b))
+        {
+            return Arg::Raw(s.into());
+        }
+
+        Arg::Raw(quote(s).into())
+    }
+
+    /// Formats a string as a quoted string, or as a literal if it cannot be
+    /// quoted. For arguments that are a `string` rather than an `astring`.
+    pub fn String(s: &'c str) -> Arg<'c> {
+        match Arg::AString(s) {
+            Arg::Raw(_) => Arg::Raw(quote(s).into()),
+            arg => arg,
+        }
+    }
+
+    /// Formats a LIST pattern. `*` and `%` are kept as wildcards, so a
+    /// pattern made of atom characters and wildcards is sent as is, and any
+    /// other is quoted like an astring.
+    pub fn ListMailbox(s: &'c str) -> Arg<'c> {
+        if !s.is_empty()
+            && s.is_ascii()
+            && s.bytes()
+                .all(|b| !b.is_ascii_control() && !b"(){ \"\\".contains(&b))
+        {
+            return Arg::Raw(s.into());
+        }
+
+        match Arg::AString(s) {
+            Arg::Raw(_) => Arg::Raw(quote(s).into()),
+            arg => arg,
+        }
+    }
+}
+
+/// Data sent as a `{n}` literal.
+#[derive(Debug, Clone)]
+pub enum Literal<'c> {
+    Bytes(Cow<'c, [u8]>),
+    /// A literal of the given size, read from the stream passed to
+    /// `Client::executeWith`.
+    Stream(u64),
+}
+
+impl<'c> Literal<'c> {
+    pub fn Len(&self) -> u64 {
+        match self {
+            Literal::Bytes(b) => b.len() as u64,
+            Literal::Stream(n) => *n,
+        }
+    }
+}
+
+impl<'c> From<&'c str> for Arg<'c> {
+    fn from(s: &'c str) -> Self {
+        Arg::Raw(s.into())
+    }
+}
+
+impl<'c> From<String> for Arg<'c> {
+    fn from(s: String) -> Self {
+        Arg::Raw(s.into())
+    }
+}
+
+impl<'c> From<Cow<'c, str>> for Arg<'c> {
+    fn from(s: Cow<'c, str>) -> Self {
+        Arg::Raw(s)
+    }
+}
+
+fn quote(s: &str) -> String {
+    format!("\"{}\"", s.replace('\\', "\\\\").replace('"', "\\\""))
+}
+
+/// A piece of an encoded command: text, followed by a literal whose data
+/// may only be sent once the server is ready for it.
+#[derive(Debug)]
+pub struct Part<'p, 'c> {
+    pub Text: String,
+    pub Literal: Option<&'p Literal<'c>>,
 }
 
 impl<'c> std::fmt::Display for Command<'c> {
@@ -17,15 +131,79 @@ impl<'c> std::fmt::Display for Command<'c> {
 }
 
 impl<'c> Command<'c> {
+    /// Splits the command at each literal. The text of a part ends where the
+    /// literal's `{n}` header goes, the last part ends with CRLF.
+    pub fn Parts(&self) -> Vec<Part<'_, 'c>> {
+        self.encode(false)
+    }
+
+    /// Like `Parts`, for a connection with UTF8=ACCEPT enabled (RFC 6855).
+    pub fn Utf8Parts(&self) -> Vec<Part<'_, 'c>> {
+        self.encode(true)
+    }
+
+    fn encode(&self, utf8: bool) -> Vec<Part<'_, 'c>> {
+        fn encode<'p, 'c>(arg: &'p Arg<'c>, utf8: bool, parts: &mut Vec<Part<'p, 'c>>) {
+            let literal = match arg {
+                Arg::Raw(s) => return parts.last_mut().unwrap().Text.push_str(s),
+                Arg::List(l) => {
+                    parts.last_mut().unwrap().Text.push('(');
+                    for (i, arg) in l.iter().enumerate() {
+                        if i > 0 {
+                            parts.last_mut().unwrap().Text.push(' ');
+                        }
+                        encode(arg, utf8, parts);
+                    }
+                    return parts.last_mut().unwrap().Text.push(')');
+                }
+                Arg::Utf8(Literal::Bytes(b)) if utf8 => {
+                    let quoted = quote(&String::from_utf8_lossy(b));
+                    return parts.last_mut().unwrap().Text.push_str(&quoted);
+                }
+                Arg::Literal(lit) | Arg::Utf8(lit) => lit,
+                Arg::Literal8(lit) => {
+                    parts.last_mut().unwrap().Text.push('~');
+                    lit
+                }
+            };
+
+            parts.last_mut().unwrap().Literal = Some(literal);
+            parts.push(Part {
+                Text: String::new(),
+                Literal: None,
+            });
+        }
+
+        let mut parts = vec![Part {
+            Text: format!("{} {}", self.Tag, self.Name),
+            Literal: None,
+        }];
+
+        for arg in self.Arguments.iter() {
+            parts.last_mut().unwrap().Text.push(' ');
+            encode(arg, utf8, &mut parts);
+        }
+
+        parts.last_mut().unwrap().Text.push_str("\r\n");
+        parts
+    }
+
+    /// Formats the whole command, with literals inlined.
     pub fn as_str(&self) -> Cow<'c, str> {
-        ([self.Tag.as_ref(), self.Name.as_ref()]
-            .iter()
-            .map(std::ops::Deref::deref)
-            .chain(self.Arguments.iter().map(std::ops::Deref::deref))
-            .collect::<Cow<'_, [&str]>>()
-            .join(" ")
-            + "\r\n")
-            .into()
+        let mut s = String::new();
+        for part in self.Parts() {
+            s.push_str(&part.Text);
+            match part.Literal {
+                Some(Literal::Bytes(b)) => {
+                    s.push_str(&format!("{{{}}}\r\n", b.len()));
+                    s.push_str(&String::from_utf8_lossy(b));
+                }
+                Some(Literal::Stream(n)) => s.push_str(&format!("{{{}}}\r\n", n)),
+                None => {}
+            }
+        }
+
+        s.into()
     }
 }
 
@@ -35,10 +213,58 @@ pub trait Commander {
 }
 
 pub mod select;
-pub use select::Select;
+pub use select::{QResync, Select};
 
 pub mod login;
 pub use login::Login;
 
 pub mod list;
-pub use list::List;
+pub use list::{List, ListReturn, ListSelection};
+
+pub mod store;
+pub use store::{Store, StoreMode};
+
+pub mod copy;
+pub use copy::{Copy, Move};
+
+pub mod expunge;
+pub use expunge::Expunge;
+
+pub mod append;
+pub use append::{Append, AppendMessage, Catenate, CatenatePart, MultiAppend};
+
+pub mod mailbox;
+pub use mailbox::{Create, Delete, Rename, Subscribe};
+
+pub mod status;
+pub use status::{Status, StatusItem};
+
+pub mod enable;
+pub use enable::Enable;
+
+pub mod namespace;
+pub use namespace::Namespace;
+
+pub mod id;
+pub use id::Id;
+
+pub mod quota;
+pub use quota::{GetQuota, GetQuotaRoot, QuotaResource, SetQuota};
+
+pub mod acl;
+pub use acl::{DeleteAcl, GetAcl, ListRights, MyRights, SetAcl};
+
+pub mod metadata;
+pub use metadata::{GetMetadata, MetadataDepth, SetMetadata};
+
+pub mod search;
+pub use search::{Search, SearchKey};
+
+pub mod sort;
+pub use sort::{Sort, SortCriterion, SortKey};
+
+pub mod thread;
+pub use thread::{Thread, ThreadAlgorithm};
+
+pub mod fetch;
+pub use fetch::{Fetch, FetchItem};
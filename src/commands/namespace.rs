@@ -0,0 +1,16 @@
+use super::{Command, Commander};
+
+/// Asks for the prefixes of the personal, other users' and shared
+/// namespaces, defined in RFC 2342. Requires the NAMESPACE capability.
+#[derive(Debug, Default)]
+pub struct Namespace;
+
+impl Commander for Namespace {
+    fn Command<'c>(&'c self) -> Command<'c> {
+        Command {
+            Tag: "a001".into(),
+            Name: "NAMESPACE".into(),
+            Arguments: Default::default(),
+        }
+    }
+}
@@ -0,0 +1,108 @@
+use super::{Arg, Command, Commander};
+use futures_lite::io;
+use std::borrow::Cow;
+
+/// A resource limited by a quota (RFC 9208).
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum QuotaResource {
+    /// Size of the messages, in units of 1024 octets.
+    Storage,
+    /// Number of messages.
+    Message,
+    /// Number of mailboxes.
+    Mailbox,
+    /// Size of the annotations, in units of 1024 octets.
+    AnnotationStorage,
+    Other(String),
+}
+
+impl QuotaResource {
+    pub fn Name(&self) -> &str {
+        match self {
+            QuotaResource::Storage => "STORAGE",
+            QuotaResource::Message => "MESSAGE",
+            QuotaResource::Mailbox => "MAILBOX",
+            QuotaResource::AnnotationStorage => "ANNOTATION-STORAGE",
+            QuotaResource::Other(name) => name,
+        }
+    }
+}
+
+impl std::str::FromStr for QuotaResource {
+    type Err = io::Error;
+
+    fn from_str(s: &str) -> io::Result<QuotaResource> {
+        Ok(match s.to_uppercase().as_str() {
+            "STORAGE" => QuotaResource::Storage,
+            "MESSAGE" => QuotaResource::Message,
+            "MAILBOX" => QuotaResource::Mailbox,
+            "ANNOTATION-STORAGE" => QuotaResource::AnnotationStorage,
+            _ => QuotaResource::Other(s.to_string()),
+        })
+    }
+}
+
+#[derive(Debug, Default)]
+pub struct GetQuota<'a> {
+    pub Root: &'a str,
+}
+
+impl<'a> Commander for GetQuota<'a> {
+    fn Command<'c>(&'c self) -> Command<'c> {
+        let mut args = Cow::<'c, [Arg<'c>]>::default();
+        args.to_mut().push(Arg::AString(self.Root));
+
+        Command {
+            Tag: "a001".into(),
+            Name: "GETQUOTA".into(),
+            Arguments: args,
+        }
+    }
+}
+
+/// Asks for the quota roots of a mailbox, and the quotas of each.
+#[derive(Debug, Default)]
+pub struct GetQuotaRoot<'a> {
+    pub Mailbox: &'a str,
+}
+
+impl<'a> Commander for GetQuotaRoot<'a> {
+    fn Command<'c>(&'c self) -> Command<'c> {
+        let mut args = Cow::<'c, [Arg<'c>]>::default();
+        args.to_mut().push(Arg::AString(self.Mailbox));
+
+        Command {
+            Tag: "a001".into(),
+            Name: "GETQUOTAROOT".into(),
+            Arguments: args,
+        }
+    }
+}
+
+/// Sets the limits of a quota root. Resources left out are no longer
+/// limited.
+#[derive(Debug, Default)]
+pub struct SetQuota<'a> {
+    pub Root: &'a str,
+    pub Limits: &'a [(QuotaResource, u64)],
+}
+
+impl<'a> Commander for SetQuota<'a> {
+    fn Command<'c>(&'c self) -> Command<'c> {
+        let mut args = Cow::<'c, [Arg<'c>]>::default();
+        args.to_mut().push(Arg::AString(self.Root));
+
+        let limits = self
+            .Limits
+            .iter()
+            .flat_map(|(resource, limit)| [resource.Name().into(), limit.to_string().into()])
+            .collect();
+        args.to_mut().push(Arg::List(limits));
+
+        Command {
+            Tag: "a001".into(),
+            Name: "SETQUOTA".into(),
+            Arguments: args,
+        }
+    }
+}
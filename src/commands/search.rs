@@ -0,0 +1,222 @@
+use super::{Arg, Command, Commander};
+use crate::SequenceSet;
+use std::borrow::Cow;
+
+/// Search criteria, as taken by SEARCH, SORT and THREAD. Keys given together in
+/// `And` must all match.
+///
+/// Dates are RFC 3501 dates such as `1-Feb-1994`.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum SearchKey<'a> {
+    All,
+    Answered,
+    Deleted,
+    Draft,
+    Flagged,
+    Seen,
+    New,
+    Old,
+    Recent,
+    Unanswered,
+    Undeleted,
+    Undraft,
+    Unflagged,
+    Unseen,
+    Keyword(&'a str),
+    Unkeyword(&'a str),
+    SeqSet(&'a SequenceSet),
+    Uid(&'a SequenceSet),
+    From(&'a str),
+    To(&'a str),
+    Cc(&'a str),
+    Bcc(&'a str),
+    Subject(&'a str),
+    Body(&'a str),
+    Text(&'a str),
+    /// A header field name and a string its value contains.
+    Header(&'a str, &'a str),
+    Before(&'a str),
+    On(&'a str),
+    Since(&'a str),
+    SentBefore(&'a str),
+    SentOn(&'a str),
+    SentSince(&'a str),
+    Larger(u32),
+    Smaller(u32),
+    /// Messages with a mod-sequence of at least the given one (RFC 7162).
+    ModSeq(u64),
+    Not(Box<SearchKey<'a>>),
+    Or(Box<SearchKey<'a>>, Box<SearchKey<'a>>),
+    And(Vec<SearchKey<'a>>),
+}
+
+impl<'a> SearchKey<'a> {
+    /// Matches messages matching both `self` and `other`.
+    pub fn AndAlso(self, other: SearchKey<'a>) -> SearchKey<'a> {
+        match self {
+            SearchKey::And(mut keys) => {
+                keys.push(other);
+                SearchKey::And(keys)
+            }
+            key => SearchKey::And(vec![key, other]),
+        }
+    }
+
+    /// Matches messages matching `self`, `other` or both.
+    pub fn OrElse(self, other: SearchKey<'a>) -> SearchKey<'a> {
+        SearchKey::Or(Box::new(self), Box::new(other))
+    }
+
+    pub fn Negated(self) -> SearchKey<'a> {
+        SearchKey::Not(Box::new(self))
+    }
+
+    /// Whether any key matches messages by mod-sequence, which requires
+    /// CONDSTORE.
+    pub fn UsesModSeq(&self) -> bool {
+        self.any(&|k| matches!(k, SearchKey::ModSeq(_)))
+    }
+
+    /// Whether all strings in the criteria are ASCII, so SEARCH needs no
+    /// CHARSET.
+    pub fn IsAscii(&self) -> bool {
+        !self.any(&|k| match k {
+            SearchKey::Keyword(s)
+            | SearchKey::Unkeyword(s)
+            | SearchKey::From(s)
+            | SearchKey::To(s)
+            | SearchKey::Cc(s)
+            | SearchKey::Bcc(s)
+            | SearchKey::Subject(s)
+            | SearchKey::Body(s)
+            | SearchKey::Text(s) => !s.is_ascii(),
+            SearchKey::Header(field, value) => !field.is_ascii() || !value.is_ascii(),
+            _ => false,
+        })
+    }
+
+    fn any(&self, f: &impl Fn(&SearchKey<'a>) -> bool) -> bool {
+        f(self)
+            || match self {
+                SearchKey::Not(key) => key.any(f),
+                SearchKey::Or(a, b) => a.any(f) || b.any(f),
+                SearchKey::And(keys) => keys.iter().any(|k| k.any(f)),
+                _ => false,
+            }
+    }
+
+    /// The command arguments for the criteria. Keys of a top-level `And`
+    /// are not parenthesized.
+    pub fn Args<'c>(&'c self) -> Vec<Arg<'c>> {
+        let mut args = Vec::new();
+        match self {
+            SearchKey::And(keys) => keys.iter().for_each(|k| k.push(&mut args)),
+            key => key.push(&mut args),
+        }
+        args
+    }
+
+    fn push<'c>(&'c self, args: &mut Vec<Arg<'c>>) {
+        let name = match self {
+            SearchKey::All => "ALL",
+            SearchKey::Answered => "ANSWERED",
+            SearchKey::Deleted => "DELETED",
+            SearchKey::Draft => "DRAFT",
+            SearchKey::Flagged => "FLAGGED",
+            SearchKey::Seen => "SEEN",
+            SearchKey::New => "NEW",
+            SearchKey::Old => "OLD",
+            SearchKey::Recent => "RECENT",
+            SearchKey::Unanswered => "UNANSWERED",
+            SearchKey::Undeleted => "UNDELETED",
+            SearchKey::Undraft => "UNDRAFT",
+            SearchKey::Unflagged => "UNFLAGGED",
+            SearchKey::Unseen => "UNSEEN",
+            SearchKey::Keyword(_) => "KEYWORD",
+            SearchKey::Unkeyword(_) => "UNKEYWORD",
+            SearchKey::SeqSet(set) => return args.push(set.to_string().into()),
+            SearchKey::Uid(_) => "UID",
+            SearchKey::From(_) => "FROM",
+            SearchKey::To(_) => "TO",
+            SearchKey::Cc(_) => "CC",
+            SearchKey::Bcc(_) => "BCC",
+            SearchKey::Subject(_) => "SUBJECT",
+            SearchKey::Body(_) => "BODY",
+            SearchKey::Text(_) => "TEXT",
+            SearchKey::Header(..) => "HEADER",
+            SearchKey::Before(_) => "BEFORE",
+            SearchKey::On(_) => "ON",
+            SearchKey::Since(_) => "SINCE",
+            SearchKey::SentBefore(_) => "SENTBEFORE",
+            SearchKey::SentOn(_) => "SENTON",
+            SearchKey::SentSince(_) => "SENTSINCE",
+            SearchKey::Larger(_) => "LARGER",
+            SearchKey::Smaller(_) => "SMALLER",
+            SearchKey::ModSeq(_) => "MODSEQ",
+            SearchKey::Not(_) => "NOT",
+            SearchKey::Or(..) => "OR",
+            SearchKey::And(keys) => {
+                let mut list = Vec::new();
+                keys.iter().for_each(|k| k.push(&mut list));
+                return args.push(Arg::List(list.into()));
+            }
+        };
+        args.push(name.into());
+
+        match self {
+            SearchKey::Keyword(s) | SearchKey::Unkeyword(s) => args.push((*s).into()),
+            SearchKey::Uid(set) => args.push(set.to_string().into()),
+            SearchKey::From(s)
+            | SearchKey::To(s)
+            | SearchKey::Cc(s)
+            | SearchKey::Bcc(s)
+            | SearchKey::Subject(s)
+            | SearchKey::Body(s)
+            | SearchKey::Text(s) => args.push(Arg::AString(s)),
+            SearchKey::Header(field, value) => {
+                args.push(Arg::AString(field));
+                args.push(Arg::AString(value));
+            }
+            SearchKey::Before(d)
+            | SearchKey::On(d)
+            | SearchKey::Since(d)
+            | SearchKey::SentBefore(d)
+            | SearchKey::SentOn(d)
+            | SearchKey::SentSince(d) => args.push((*d).into()),
+            SearchKey::Larger(n) | SearchKey::Smaller(n) => args.push(n.to_string().into()),
+            SearchKey::ModSeq(n) => args.push(n.to_string().into()),
+            SearchKey::Not(key) => key.push(args),
+            SearchKey::Or(a, b) => {
+                a.push(args);
+                b.push(args);
+            }
+            _ => {}
+        }
+    }
+}
+
+/// SEARCH and UID SEARCH. Criteria with non-ASCII strings are sent with
+/// `CHARSET UTF-8`.
+#[derive(Debug)]
+pub struct Search<'a> {
+    pub Search: &'a SearchKey<'a>,
+    pub Uid: bool,
+}
+
+impl<'a> Commander for Search<'a> {
+    fn Command<'c>(&'c self) -> Command<'c> {
+        let mut args = Cow::<'c, [Arg<'c>]>::default();
+
+        if !self.Search.IsAscii() {
+            args.to_mut().push("CHARSET".into());
+            args.to_mut().push("UTF-8".into());
+        }
+        args.to_mut().extend(self.Search.Args());
+
+        Command {
+            Tag: "a001".into(),
+            Name: if self.Uid { "UID SEARCH" } else { "SEARCH" }.into(),
+            Arguments: args,
+        }
+    }
+}
@@ -1,16 +1,63 @@
-use super::{Command, Commander};
+use super::{Arg, Command, Commander};
+use crate::SequenceSet;
 use std::borrow::Cow;
 
+/// What the client knows of a mailbox from a previous session, for a
+/// SELECT with QRESYNC (RFC 7162).
+#[derive(Debug, Clone, Copy)]
+pub struct QResync<'a> {
+    pub UidValidity: u32,
+    /// The `HighestModSeq` of the last session.
+    pub ModSeq: u64,
+    /// The UIDs the client has, so VANISHED is limited to them.
+    pub KnownUids: Option<&'a SequenceSet>,
+    /// Sequence numbers and the UIDs they had, for the server to find which
+    /// of the known UIDs were expunged. Needs `KnownUids`.
+    pub SeqMatch: Option<(&'a SequenceSet, &'a SequenceSet)>,
+}
+
+impl<'a> QResync<'a> {
+    fn arg<'c>(&'c self) -> Arg<'c> {
+        let mut params: Vec<Arg<'c>> = vec![
+            self.UidValidity.to_string().into(),
+            self.ModSeq.to_string().into(),
+        ];
+        if let Some(uids) = self.KnownUids {
+            params.push(uids.to_string().into());
+
+            if let Some((seqs, uids)) = self.SeqMatch {
+                params.push(Arg::List(
+                    vec![seqs.to_string().into(), uids.to_string().into()].into(),
+                ));
+            }
+        }
+
+        Arg::List(vec!["QRESYNC".into(), Arg::List(params.into())].into())
+    }
+}
+
 #[derive(Debug, Default)]
 pub struct Select<'a> {
     pub Mailbox: &'a str,
     pub ReadOnly: bool,
+    /// Turns on CONDSTORE for the session (RFC 7162), so the server reports
+    /// `[HIGHESTMODSEQ]` and mod-sequences.
+    pub CondStore: bool,
+    /// Asks for the changes since a previous session. QRESYNC must have
+    /// been enabled.
+    pub QResync: Option<QResync<'a>>,
 }
 
 impl<'a> Commander for Select<'a> {
     fn Command<'c>(&'c self) -> Command<'c> {
-        let mut args = Cow::<'c, [Cow<'c, str>]>::default();
-        args.to_mut().push(self.Mailbox.into());
+        let mut args = Cow::<'c, [Arg<'c>]>::default();
+        args.to_mut().push(Arg::AString(self.Mailbox));
+        if let Some(qresync) = &self.QResync {
+            args.to_mut().push(qresync.arg());
+        } else if self.CondStore {
+            args.to_mut()
+                .push(Arg::List(vec!["CONDSTORE".into()].into()));
+        }
 
         Command {
             Tag: "a001".into(),
@@ -0,0 +1,82 @@
+use super::{Arg, Command, Commander, SearchKey};
+use std::borrow::Cow;
+
+/// What SORT orders messages by (RFC 5256, RFC 5957).
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum SortKey {
+    Arrival,
+    Cc,
+    Date,
+    From,
+    Size,
+    Subject,
+    To,
+    /// The display name of the sender, requires SORT=DISPLAY.
+    DisplayFrom,
+    /// The display name of the first recipient, requires SORT=DISPLAY.
+    DisplayTo,
+}
+
+impl SortKey {
+    pub fn Name(&self) -> &'static str {
+        match self {
+            SortKey::Arrival => "ARRIVAL",
+            SortKey::Cc => "CC",
+            SortKey::Date => "DATE",
+            SortKey::From => "FROM",
+            SortKey::Size => "SIZE",
+            SortKey::Subject => "SUBJECT",
+            SortKey::To => "TO",
+            SortKey::DisplayFrom => "DISPLAYFROM",
+            SortKey::DisplayTo => "DISPLAYTO",
+        }
+    }
+}
+
+/// A sort key, with `Reverse` for descending order.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct SortCriterion {
+    pub Key: SortKey,
+    pub Reverse: bool,
+}
+
+impl From<SortKey> for SortCriterion {
+    fn from(key: SortKey) -> SortCriterion {
+        SortCriterion {
+            Key: key,
+            Reverse: false,
+        }
+    }
+}
+
+/// SORT and UID SORT, defined in RFC 5256. Requires the SORT capability.
+/// Later criteria break the ties of the earlier ones.
+#[derive(Debug)]
+pub struct Sort<'a> {
+    pub Criteria: &'a [SortCriterion],
+    pub Search: &'a SearchKey<'a>,
+    pub Uid: bool,
+}
+
+impl<'a> Commander for Sort<'a> {
+    fn Command<'c>(&'c self) -> Command<'c> {
+        let mut args = Cow::<'c, [Arg<'c>]>::default();
+
+        let mut criteria = Vec::new();
+        for c in self.Criteria.iter() {
+            if c.Reverse {
+                criteria.push("REVERSE".into());
+            }
+            criteria.push(c.Key.Name().into());
+        }
+        args.to_mut().push(Arg::List(criteria.into()));
+        args.to_mut().push("UTF-8".into());
+        args.to_mut().extend(self.Search.Args());
+
+        Command {
+            Tag: "a001".into(),
+            Name: if self.Uid { "UID SORT" } else { "SORT" }.into(),
+            Arguments: args,
+        }
+    }
+}
@@ -0,0 +1,76 @@
+use super::{Arg, Command, Commander};
+use std::borrow::Cow;
+
+/// A data item requested with STATUS.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum StatusItem {
+    Messages,
+    Recent,
+    UidNext,
+    UidValidity,
+    Unseen,
+    /// Requires CONDSTORE, or QRESYNC which implies it (RFC 7162).
+    HighestModSeq,
+    /// Requires STATUS=SIZE (RFC 8438).
+    Size,
+    /// Requires IMAP4rev2 (RFC 9051).
+    Deleted,
+}
+
+impl StatusItem {
+    pub fn Name(&self) -> &'static str {
+        match self {
+            StatusItem::Messages => "MESSAGES",
+            StatusItem::Recent => "RECENT",
+            StatusItem::UidNext => "UIDNEXT",
+            StatusItem::UidValidity => "UIDVALIDITY",
+            StatusItem::Unseen => "UNSEEN",
+            StatusItem::HighestModSeq => "HIGHESTMODSEQ",
+            StatusItem::Size => "SIZE",
+            StatusItem::Deleted => "DELETED",
+        }
+    }
+
+    /// The capability the server must advertise for this item, if any.
+    pub fn Capability(&self) -> Option<&'static str> {
+        match self {
+            StatusItem::HighestModSeq => Some("CONDSTORE"),
+            StatusItem::Size => Some("STATUS=SIZE"),
+            StatusItem::Deleted => Some("IMAP4rev2"),
+            _ => None,
+        }
+    }
+
+    /// Formats items as a parenthesized list suitable for a command argument.
+    pub fn FormatList(items: &[StatusItem]) -> String {
+        format!(
+            "({})",
+            items
+                .iter()
+                .map(StatusItem::Name)
+                .collect::<Vec<_>>()
+                .join(" ")
+        )
+    }
+}
+
+#[derive(Debug, Default)]
+pub struct Status<'a> {
+    pub Mailbox: &'a str,
+    pub Items: &'a [StatusItem],
+}
+
+impl<'a> Commander for Status<'a> {
+    fn Command<'c>(&'c self) -> Command<'c> {
+        let mut args = Cow::<'c, [Arg<'c>]>::default();
+        args.to_mut().push(Arg::AString(self.Mailbox));
+        args.to_mut()
+            .push(StatusItem::FormatList(self.Items).into());
+
+        Command {
+            Tag: "a001".into(),
+            Name: "STATUS".into(),
+            Arguments: args,
+        }
+    }
+}
@@ -0,0 +1,67 @@
+use super::{Arg, Command, Commander};
+use crate::{
+    flag::{self, Flag},
+    SequenceSet,
+};
+use std::borrow::Cow;
+
+/// How a STORE command changes the flags of the messages.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum StoreMode {
+    /// `FLAGS`: replace the flags.
+    Replace,
+    /// `+FLAGS`: add the flags.
+    Add,
+    /// `-FLAGS`: remove the flags.
+    Remove,
+}
+
+#[derive(Debug)]
+pub struct Store<'a> {
+    pub SeqSet: &'a SequenceSet,
+    pub Uid: bool,
+    pub Mode: StoreMode,
+    /// Asks the server not to send the new flags back (`.SILENT`).
+    pub Silent: bool,
+    pub Flags: &'a [Flag],
+    /// Only changes messages whose mod-sequence is not higher (RFC 7162).
+    /// The others are reported in a `[MODIFIED]` response code.
+    pub UnchangedSince: Option<u64>,
+}
+
+impl<'a> Store<'a> {
+    pub fn Item(&self) -> String {
+        let mut item = match self.Mode {
+            StoreMode::Replace => "FLAGS",
+            StoreMode::Add => "+FLAGS",
+            StoreMode::Remove => "-FLAGS",
+        }
+        .to_string();
+
+        if self.Silent {
+            item.push_str(".SILENT");
+        }
+
+        item
+    }
+}
+
+impl<'a> Commander for Store<'a> {
+    fn Command<'c>(&'c self) -> Command<'c> {
+        let mut args = Cow::<'c, [Arg<'c>]>::default();
+        args.to_mut().push(self.SeqSet.to_string().into());
+        if let Some(modSeq) = self.UnchangedSince {
+            args.to_mut().push(Arg::List(
+                vec!["UNCHANGEDSINCE".into(), modSeq.to_string().into()].into(),
+            ));
+        }
+        args.to_mut().push(self.Item().into());
+        args.to_mut().push(flag::FormatFlags(self.Flags).into());
+
+        Command {
+            Tag: "a001".into(),
+            Name: if self.Uid { "UID STORE" } else { "STORE" }.into(),
+            Arguments: args,
+        }
+    }
+}
@@ -0,0 +1,67 @@
+use super::{Arg, Command, Commander, SearchKey};
+use std::{borrow::Cow, str::FromStr};
+
+use futures_lite::io;
+
+/// How THREAD groups messages (RFC 5256, RFC 5957).
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+pub enum ThreadAlgorithm {
+    /// Groups by base subject, threads are flat under their first message.
+    OrderedSubject,
+    /// Follows the Message-ID, In-Reply-To and References headers.
+    References,
+    /// Like REFERENCES, but orders threads by their latest message and
+    /// doesn't merge by subject.
+    Refs,
+}
+
+impl ThreadAlgorithm {
+    pub fn Name(&self) -> &'static str {
+        match self {
+            ThreadAlgorithm::OrderedSubject => "ORDEREDSUBJECT",
+            ThreadAlgorithm::References => "REFERENCES",
+            ThreadAlgorithm::Refs => "REFS",
+        }
+    }
+}
+
+impl FromStr for ThreadAlgorithm {
+    type Err = io::Error;
+
+    fn from_str(s: &str) -> io::Result<ThreadAlgorithm> {
+        match s.to_ascii_uppercase().as_str() {
+            "ORDEREDSUBJECT" => Ok(ThreadAlgorithm::OrderedSubject),
+            "REFERENCES" => Ok(ThreadAlgorithm::References),
+            "REFS" => Ok(ThreadAlgorithm::Refs),
+            _ => Err(io::Error::new(
+                io::ErrorKind::Other,
+                format!("unknown thread algorithm: {}", s),
+            )),
+        }
+    }
+}
+
+/// THREAD and UID THREAD, defined in RFC 5256. Requires the THREAD=
+/// capability of the algorithm.
+#[derive(Debug)]
+pub struct Thread<'a> {
+    pub Algorithm: ThreadAlgorithm,
+    pub Search: &'a SearchKey<'a>,
+    pub Uid: bool,
+}
+
+impl<'a> Commander for Thread<'a> {
+    fn Command<'c>(&'c self) -> Command<'c> {
+        let mut args = Cow::<'c, [Arg<'c>]>::default();
+
+        args.to_mut().push(self.Algorithm.Name().into());
+        args.to_mut().push("UTF-8".into());
+        args.to_mut().extend(self.Search.Args());
+
+        Command {
+            Tag: "a001".into(),
+            Name: if self.Uid { "UID THREAD" } else { "THREAD" }.into(),
+            Arguments: args,
+        }
+    }
+}
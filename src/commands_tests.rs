@@ -0,0 +1,579 @@
+use crate::{
+    commands::{
+        self, Arg, CatenatePart, Commander, FetchItem, ListReturn, ListSelection, Literal,
+        MetadataDepth, QResync, QuotaResource, SearchKey, SortCriterion, SortKey, StatusItem,
+        StoreMode, ThreadAlgorithm,
+    },
+    responses::MailboxAttribute,
+    Flag, Rights, SequenceSet,
+};
+
+#[test]
+fn TestStore_Command() {
+    let set = "1:3,7".parse::<SequenceSet>().unwrap();
+
+    let cmd = commands::Store {
+        SeqSet: &set,
+        Uid: false,
+        Mode: StoreMode::Add,
+        Silent: true,
+        Flags: &[Flag::Seen, Flag::Keyword("$Done".into())],
+        UnchangedSince: None,
+    };
+    debug_assert!(cmd.Command().as_str() == "a001 STORE 1:3,7 +FLAGS.SILENT (\\Seen $Done)\r\n");
+
+    let cmd = commands::Store {
+        SeqSet: &set,
+        Uid: true,
+        Mode: StoreMode::Remove,
+        Silent: false,
+        Flags: &[Flag::Deleted],
+        UnchangedSince: None,
+    };
+    debug_assert!(cmd.Command().as_str() == "a001 UID STORE 1:3,7 -FLAGS (\\Deleted)\r\n");
+
+    let cmd = commands::Store {
+        SeqSet: &set,
+        Uid: false,
+        Mode: StoreMode::Replace,
+        Silent: true,
+        Flags: &[Flag::Seen],
+        UnchangedSince: Some(12121230045),
+    };
+    debug_assert!(
+        cmd.Command().as_str()
+            == "a001 STORE 1:3,7 (UNCHANGEDSINCE 12121230045) FLAGS.SILENT (\\Seen)\r\n"
+    );
+}
+
+#[test]
+fn TestCopyMove_Command() {
+    let set = "2:4".parse::<SequenceSet>().unwrap();
+
+    let cmd = commands::Copy {
+        SeqSet: &set,
+        Uid: false,
+        Mailbox: "Archive",
+    };
+    debug_assert!(cmd.Command().as_str() == "a001 COPY 2:4 Archive\r\n");
+
+    let cmd = commands::Move {
+        SeqSet: &set,
+        Uid: true,
+        Mailbox: "Archive",
+    };
+    debug_assert!(cmd.Command().as_str() == "a001 UID MOVE 2:4 Archive\r\n");
+}
+
+#[test]
+fn TestExpunge_Command() {
+    debug_assert!(commands::Expunge::default().Command().as_str() == "a001 EXPUNGE\r\n");
+
+    let set = "10,12".parse::<SequenceSet>().unwrap();
+    let cmd = commands::Expunge { Uids: Some(&set) };
+    debug_assert!(cmd.Command().as_str() == "a001 UID EXPUNGE 10,12\r\n");
+}
+
+#[test]
+fn TestArg_AString() {
+    let raw = |a: Arg| match a {
+        Arg::Raw(s) => s.into_owned(),
+        _ => "<literal>".into(),
+    };
+
+    debug_assert!(raw(Arg::AString("INBOX")) == "INBOX");
+    debug_assert!(raw(Arg::AString("Sent Items")) == "\"Sent Items\"");
+    debug_assert!(raw(Arg::AString("")) == "\"\"");
+    debug_assert!(raw(Arg::AString("a\"b\\c")) == "\"a\\\"b\\\\c\"");
+    debug_assert!(raw(Arg::AString("Entwürfe")) == "<literal>");
+}
+
+#[test]
+fn TestAppend_Command() {
+    let cmd = commands::Append {
+        Mailbox: "Sent Items",
+        Flags: &[Flag::Seen],
+        Date: Some("17-Jul-1996 02:44:25 -0700"),
+        Message: Literal::Bytes(b"Subject: hi\r\n\r\nhello\r\n"[..].into()),
+        Utf8: false,
+    };
+
+    let cmd = cmd.Command();
+    let parts = cmd.Parts();
+    debug_assert!(parts.len() == 2);
+    debug_assert!(
+        parts[0].Text == "a001 APPEND \"Sent Items\" (\\Seen) \"17-Jul-1996 02:44:25 -0700\" "
+    );
+    debug_assert!(parts[0].Literal.map_or(false, |l| l.Len() == 22));
+    debug_assert!(parts[1].Text == "\r\n" && parts[1].Literal.is_none());
+
+    let cmd = commands::Append {
+        Mailbox: "Drafts",
+        Flags: &[],
+        Date: None,
+        Message: Literal::Stream(1024),
+        Utf8: false,
+    };
+    debug_assert!(cmd.Command().as_str() == "a001 APPEND Drafts {1024}\r\n\r\n");
+}
+
+#[test]
+fn TestMultiAppend_Command() {
+    let messages = [
+        commands::AppendMessage {
+            Flags: &[Flag::Seen],
+            Date: None,
+            Message: b"one",
+        },
+        commands::AppendMessage {
+            Flags: &[],
+            Date: Some("17-Jul-1996 02:44:25 -0700"),
+            Message: b"two",
+        },
+    ];
+
+    let cmd = commands::MultiAppend {
+        Mailbox: "Archive",
+        Messages: &messages,
+        Utf8: false,
+    };
+    debug_assert!(
+        cmd.Command().as_str()
+            == "a001 APPEND Archive (\\Seen) {3}\r\none \"17-Jul-1996 02:44:25 -0700\" {3}\r\ntwo\r\n"
+    );
+}
+
+#[test]
+fn TestCatenate_Command() {
+    let cmd = commands::Catenate {
+        Mailbox: "Drafts",
+        Flags: &[Flag::Draft],
+        Date: None,
+        Parts: &[
+            CatenatePart::Url("/Drafts;UIDVALIDITY=385759045/;UID=20/;section=HEADER"),
+            CatenatePart::Text(b"\r\nbody\r\n"),
+            CatenatePart::Url("/Drafts;UIDVALIDITY=385759045/;UID=20/;section=1.MIME"),
+        ],
+    };
+
+    let cmd = cmd.Command();
+    let parts = cmd.Parts();
+    debug_assert!(parts.len() == 2);
+    debug_assert!(
+        parts[0].Text
+            == "a001 APPEND Drafts (\\Draft) CATENATE (URL /Drafts;UIDVALIDITY=385759045/;UID=20/;section=HEADER TEXT "
+    );
+    debug_assert!(parts[0].Literal.map_or(false, |l| l.Len() == 8));
+    debug_assert!(
+        parts[1].Text == " URL /Drafts;UIDVALIDITY=385759045/;UID=20/;section=1.MIME)\r\n"
+    );
+}
+
+#[test]
+fn TestMailbox_Commands() {
+    let cmd = commands::Create {
+        Mailbox: "Sent Items",
+        SpecialUse: &[MailboxAttribute::Sent],
+    };
+    debug_assert!(cmd.Command().as_str() == "a001 CREATE \"Sent Items\" (USE (\\Sent))\r\n");
+
+    let cmd = commands::Rename {
+        Existing: "Work",
+        New: "Old Work",
+    };
+    debug_assert!(cmd.Command().as_str() == "a001 RENAME Work \"Old Work\"\r\n");
+
+    let cmd = commands::Subscribe {
+        Mailbox: "Work/Old",
+        Unsubscribe: true,
+    };
+    debug_assert!(cmd.Command().as_str() == "a001 UNSUBSCRIBE Work/Old\r\n");
+
+    let cmd = commands::List {
+        Reference: "",
+        Mailbox: "*",
+        Subscribed: true,
+        ..Default::default()
+    };
+    debug_assert!(cmd.Command().as_str() == "a001 LSUB \"\" *\r\n");
+
+    let cmd = commands::List {
+        Reference: "Archive 2024/",
+        Mailbox: "Sent Items/%",
+        Subscribed: true,
+        ..Default::default()
+    };
+    debug_assert!(cmd.Command().as_str() == "a001 LSUB \"Archive 2024/\" \"Sent Items/%\"\r\n");
+
+    let cmd = commands::List {
+        Reference: "Archive/2024",
+        Mailbox: "*",
+        ..Default::default()
+    };
+    debug_assert!(cmd.Command().as_str() == "a001 LIST Archive/2024 *\r\n");
+}
+
+#[test]
+fn TestStatus_Command() {
+    let cmd = commands::Status {
+        Mailbox: "Sent Items",
+        Items: &[StatusItem::Messages, StatusItem::Unseen, StatusItem::Size],
+    };
+    debug_assert!(
+        cmd.Command().as_str() == "a001 STATUS \"Sent Items\" (MESSAGES UNSEEN SIZE)\r\n"
+    );
+}
+
+#[test]
+fn TestListExtended_Command() {
+    let cmd = commands::List {
+        Reference: "",
+        Mailbox: "INBOX",
+        Patterns: &["Drafts", "Sent/%"],
+        Selection: &[ListSelection::Subscribed, ListSelection::RecursiveMatch],
+        Return: &[
+            ListReturn::Children,
+            ListReturn::Status(&[StatusItem::Messages, StatusItem::Unseen]),
+        ],
+        ..Default::default()
+    };
+    debug_assert!(
+        cmd.Command().as_str()
+            == "a001 LIST (SUBSCRIBED RECURSIVEMATCH) \"\" (INBOX Drafts Sent/%) RETURN (CHILDREN STATUS (MESSAGES UNSEEN))\r\n"
+    );
+
+    let cmd = commands::List {
+        Reference: "",
+        Mailbox: "*",
+        Return: &[ListReturn::SpecialUse],
+        ..Default::default()
+    };
+    debug_assert!(cmd.Command().as_str() == "a001 LIST \"\" * RETURN (SPECIAL-USE)\r\n");
+
+    let cmd = commands::List {
+        Reference: "",
+        Mailbox: "Sent Items",
+        Patterns: &["Sent Items/%", "Say \"hi\"*", "Entw&APw-rfe"],
+        Return: &[ListReturn::Children],
+        ..Default::default()
+    };
+    debug_assert!(
+        cmd.Command().as_str()
+            == "a001 LIST \"\" (\"Sent Items\" \"Sent Items/%\" \"Say \\\"hi\\\"*\" Entw&APw-rfe) RETURN (CHILDREN)\r\n"
+    );
+
+    let cmd = commands::List {
+        Reference: "",
+        Mailbox: "Sent Items/*",
+        ..Default::default()
+    };
+    debug_assert!(cmd.Command().as_str() == "a001 LIST \"\" \"Sent Items/*\"\r\n");
+}
+
+#[test]
+fn TestEnable_Command() {
+    let cmd = commands::Enable {
+        Capabilities: &["UTF8=ACCEPT", "CONDSTORE"],
+    };
+    debug_assert!(cmd.Command().as_str() == "a001 ENABLE UTF8=ACCEPT CONDSTORE\r\n");
+}
+
+#[test]
+fn TestUtf8_Command() {
+    let cmd = commands::Select {
+        Mailbox: "Entwürfe \"alt\"",
+        ReadOnly: false,
+        CondStore: false,
+        QResync: None,
+    };
+    let cmd = cmd.Command();
+
+    let parts = cmd.Parts();
+    debug_assert!(parts.len() == 2 && parts[0].Text == "a001 SELECT ");
+
+    let parts = cmd.Utf8Parts();
+    debug_assert!(parts.len() == 1);
+    debug_assert!(parts[0].Text == "a001 SELECT \"Entwürfe \\\"alt\\\"\"\r\n");
+
+    let cmd = commands::Append {
+        Mailbox: "INBOX",
+        Flags: &[],
+        Date: None,
+        Message: Literal::Bytes("Subject: Grüße\r\n\r\n".as_bytes().into()),
+        Utf8: true,
+    };
+    debug_assert!(
+        cmd.Command().as_str() == "a001 APPEND INBOX UTF8 (~{20}\r\nSubject: Grüße\r\n\r\n)\r\n"
+    );
+}
+
+#[test]
+fn TestId_Command() {
+    let cmd = commands::Id {
+        Params: Some(&[("name", Some("imap-rs")), ("version", Some("0.1.0"))]),
+    };
+    debug_assert!(
+        cmd.Command().as_str() == "a001 ID (\"name\" \"imap-rs\" \"version\" \"0.1.0\")\r\n"
+    );
+
+    let cmd = commands::Id {
+        Params: Some(&[("name", Some("imap-rs")), ("os", None)]),
+    };
+    debug_assert!(cmd.Command().as_str() == "a001 ID (\"name\" \"imap-rs\" \"os\" NIL)\r\n");
+
+    let cmd = commands::Id { Params: None };
+    debug_assert!(cmd.Command().as_str() == "a001 ID NIL\r\n");
+
+    let cmd = commands::Id { Params: Some(&[]) };
+    debug_assert!(cmd.Command().as_str() == "a001 ID NIL\r\n");
+}
+
+#[test]
+fn TestQuota_Commands() {
+    let cmd = commands::GetQuotaRoot { Mailbox: "INBOX" };
+    debug_assert!(cmd.Command().as_str() == "a001 GETQUOTAROOT INBOX\r\n");
+
+    let cmd = commands::GetQuota { Root: "" };
+    debug_assert!(cmd.Command().as_str() == "a001 GETQUOTA \"\"\r\n");
+
+    let cmd = commands::SetQuota {
+        Root: "",
+        Limits: &[
+            (QuotaResource::Storage, 512),
+            (QuotaResource::Message, 1000),
+        ],
+    };
+    debug_assert!(cmd.Command().as_str() == "a001 SETQUOTA \"\" (STORAGE 512 MESSAGE 1000)\r\n");
+}
+
+#[test]
+fn TestAcl_Commands() {
+    let cmd = commands::SetAcl {
+        Mailbox: "Shared/Team",
+        Identifier: "fred",
+        Mode: StoreMode::Add,
+        Rights: Rights::Read | Rights::Insert,
+    };
+    debug_assert!(cmd.Command().as_str() == "a001 SETACL Shared/Team fred +ri\r\n");
+
+    let cmd = commands::SetAcl {
+        Mailbox: "INBOX",
+        Identifier: "-anyone",
+        Mode: StoreMode::Replace,
+        Rights: Rights::default(),
+    };
+    debug_assert!(cmd.Command().as_str() == "a001 SETACL INBOX -anyone \"\"\r\n");
+
+    let cmd = commands::ListRights {
+        Mailbox: "Team Box",
+        Identifier: "smith",
+    };
+    debug_assert!(cmd.Command().as_str() == "a001 LISTRIGHTS \"Team Box\" smith\r\n");
+
+    let cmd = commands::MyRights { Mailbox: "INBOX" };
+    debug_assert!(cmd.Command().as_str() == "a001 MYRIGHTS INBOX\r\n");
+}
+
+#[test]
+fn TestMetadata_Commands() {
+    let cmd = commands::GetMetadata {
+        Mailbox: "INBOX",
+        Entries: &["/private/comment", "/shared/vendor/x/color"],
+        MaxSize: Some(1024),
+        Depth: MetadataDepth::Infinity,
+    };
+    debug_assert!(
+        cmd.Command().as_str()
+            == "a001 GETMETADATA (MAXSIZE 1024 DEPTH infinity) INBOX (/private/comment /shared/vendor/x/color)\r\n"
+    );
+
+    let cmd = commands::GetMetadata {
+        Mailbox: "",
+        Entries: &["/shared/admin"],
+        ..Default::default()
+    };
+    debug_assert!(cmd.Command().as_str() == "a001 GETMETADATA \"\" (/shared/admin)\r\n");
+
+    let cmd = commands::SetMetadata {
+        Mailbox: "INBOX",
+        Entries: &[
+            ("/private/comment", Some(b"My new comment")),
+            ("/shared/comment", None),
+            ("/private/x", Some(b"a\x00b")),
+        ],
+    };
+    debug_assert!(
+        cmd.Command().as_str()
+            == "a001 SETMETADATA INBOX (/private/comment \"My new comment\" /shared/comment NIL /private/x ~{3}\r\na\x00b)\r\n"
+    );
+}
+
+#[test]
+fn TestSearchKey_Args() {
+    let set = "1:100".parse::<SequenceSet>().unwrap();
+    let key = SearchKey::Unseen
+        .AndAlso(SearchKey::From("Smith").OrElse(SearchKey::Subject("Weekly report")))
+        .AndAlso(SearchKey::Deleted.Negated())
+        .AndAlso(SearchKey::And(vec![
+            SearchKey::Uid(&set),
+            SearchKey::Since("1-Feb-1994"),
+        ]));
+
+    let cmd = commands::Sort {
+        Criteria: &[
+            SortCriterion {
+                Key: SortKey::Date,
+                Reverse: true,
+            },
+            SortKey::Subject.into(),
+        ],
+        Search: &key,
+        Uid: true,
+    };
+    debug_assert!(
+        cmd.Command().as_str()
+            == "a001 UID SORT (REVERSE DATE SUBJECT) UTF-8 UNSEEN OR FROM Smith SUBJECT \"Weekly report\" NOT DELETED (UID 1:100 SINCE 1-Feb-1994)\r\n"
+    );
+
+    let cmd = commands::Sort {
+        Criteria: &[SortKey::DisplayFrom.into()],
+        Search: &SearchKey::All,
+        Uid: false,
+    };
+    debug_assert!(cmd.Command().as_str() == "a001 SORT (DISPLAYFROM) UTF-8 ALL\r\n");
+}
+
+#[test]
+fn TestThread_Command() {
+    let cmd = commands::Thread {
+        Algorithm: ThreadAlgorithm::References,
+        Search: &SearchKey::Since("5-Mar-2000").AndAlso(SearchKey::Undeleted),
+        Uid: true,
+    };
+    debug_assert!(
+        cmd.Command().as_str() == "a001 UID THREAD REFERENCES UTF-8 SINCE 5-Mar-2000 UNDELETED\r\n"
+    );
+
+    debug_assert!(
+        "orderedsubject".parse::<ThreadAlgorithm>().unwrap() == ThreadAlgorithm::OrderedSubject
+    );
+    debug_assert!("X-UNKNOWN".parse::<ThreadAlgorithm>().is_err());
+}
+
+#[test]
+fn TestFetch_Command() {
+    let set = "1:*".parse::<SequenceSet>().unwrap();
+    let cmd = commands::Fetch {
+        SeqSet: &set,
+        Uid: true,
+        Items: &[
+            FetchItem::Uid,
+            FetchItem::Envelope,
+            FetchItem::HeaderFields(&["REFERENCES", "LIST-ID"]),
+        ],
+        ChangedSince: None,
+        Vanished: false,
+    };
+    debug_assert!(
+        cmd.Command().as_str()
+            == "a001 UID FETCH 1:* (UID ENVELOPE BODY.PEEK[HEADER.FIELDS (REFERENCES LIST-ID)])\r\n"
+    );
+}
+
+#[test]
+fn TestCondStore_Commands() {
+    let cmd = commands::Select {
+        Mailbox: "INBOX",
+        ReadOnly: true,
+        CondStore: true,
+        QResync: None,
+    };
+    debug_assert!(cmd.Command().as_str() == "a001 EXAMINE INBOX (CONDSTORE)\r\n");
+
+    let set = "1:*".parse::<SequenceSet>().unwrap();
+    let cmd = commands::Fetch {
+        SeqSet: &set,
+        Uid: true,
+        Items: &[FetchItem::Flags, FetchItem::ModSeq],
+        ChangedSince: Some(12345),
+        Vanished: false,
+    };
+    debug_assert!(
+        cmd.Command().as_str() == "a001 UID FETCH 1:* (FLAGS MODSEQ) (CHANGEDSINCE 12345)\r\n"
+    );
+
+    let key = SearchKey::ModSeq(620162338).AndAlso(SearchKey::Seen);
+    debug_assert!(key.Args().len() == 3);
+    let cmd = commands::Sort {
+        Criteria: &[SortKey::Arrival.into()],
+        Search: &key,
+        Uid: false,
+    };
+    debug_assert!(cmd.Command().as_str() == "a001 SORT (ARRIVAL) UTF-8 MODSEQ 620162338 SEEN\r\n");
+
+    debug_assert!(key.UsesModSeq());
+    debug_assert!(!SearchKey::Seen.Negated().UsesModSeq());
+    let cmd = commands::Search {
+        Search: &key,
+        Uid: true,
+    };
+    debug_assert!(cmd.Command().as_str() == "a001 UID SEARCH MODSEQ 620162338 SEEN\r\n");
+
+    let key = SearchKey::Subject("Grüße").OrElse(SearchKey::Unseen);
+    debug_assert!(!key.IsAscii());
+    let cmd = commands::Search {
+        Search: &key,
+        Uid: false,
+    };
+    debug_assert!(cmd
+        .Command()
+        .as_str()
+        .starts_with("a001 SEARCH CHARSET UTF-8 OR SUBJECT "));
+}
+
+#[test]
+fn TestQResync_Commands() {
+    let known = "41,43:211,214:541".parse::<SequenceSet>().unwrap();
+    let seqs = "1:5".parse::<SequenceSet>().unwrap();
+    let uids = "41,43,50:52".parse::<SequenceSet>().unwrap();
+
+    let mut qresync = QResync {
+        UidValidity: 67890007,
+        ModSeq: 90060115194045000,
+        KnownUids: None,
+        SeqMatch: Some((&seqs, &uids)),
+    };
+    let cmd = commands::Select {
+        Mailbox: "INBOX",
+        ReadOnly: false,
+        CondStore: true,
+        QResync: Some(qresync),
+    };
+    debug_assert!(
+        cmd.Command().as_str() == "a001 SELECT INBOX (QRESYNC (67890007 90060115194045000))\r\n"
+    );
+
+    qresync.KnownUids = Some(&known);
+    let cmd = commands::Select {
+        Mailbox: "INBOX",
+        ReadOnly: false,
+        CondStore: false,
+        QResync: Some(qresync),
+    };
+    debug_assert!(
+        cmd.Command().as_str()
+            == "a001 SELECT INBOX (QRESYNC (67890007 90060115194045000 41,43:211,214:541 (1:5 41,43,50:52)))\r\n"
+    );
+
+    let cmd = commands::Fetch {
+        SeqSet: &known,
+        Uid: true,
+        Items: &[FetchItem::Flags],
+        ChangedSince: Some(12345),
+        Vanished: true,
+    };
+    debug_assert!(
+        cmd.Command().as_str()
+            == "a001 UID FETCH 41,43:211,214:541 (FLAGS) (CHANGEDSINCE 12345 VANISHED)\r\n"
+    );
+}
@@ -0,0 +1,103 @@
+//! Parsing of message dates, for ordering messages by date.
+
+const months: [&str; 12] = [
+    "jan", "feb", "mar", "apr", "may", "jun", "jul", "aug", "sep", "oct", "nov", "dec",
+];
+
+/// Parses an RFC 5322 date, such as `Mon, 7 Feb 1994 21:52:25 -0800`, or an
+/// INTERNALDATE, such as `17-Jul-1996 02:44:25 -0700`, into seconds since the
+/// Unix epoch, so dates in different zones compare correctly.
+///
+/// Obsolete forms are accepted: two-digit years, a missing day of week or
+/// seconds, and zone names such as `PST`. A missing or unknown zone is
+/// taken as UTC.
+pub fn Parse(s: &str) -> Option<i64> {
+    // Drop comments, such as "(PST)"
+    let mut text = String::with_capacity(s.len());
+    let mut depth = 0;
+    for c in s.chars() {
+        match c {
+            '(' => depth += 1,
+            ')' if depth > 0 => depth -= 1,
+            _ if depth == 0 => text.push(c),
+            _ => {}
+        }
+    }
+
+    let mut tokens: Vec<&str> = text
+        .split(|c: char| c.is_whitespace() || c == ',')
+        .filter(|t| !t.is_empty())
+        .collect();
+    if tokens.first()?.chars().all(|c| c.is_ascii_alphabetic()) {
+        tokens.remove(0);
+    }
+    // The INTERNALDATE form, 17-Jul-1996
+    if tokens.first()?.matches('-').count() == 2 {
+        let first = tokens.remove(0);
+        for (i, part) in first.split('-').enumerate() {
+            tokens.insert(i, part);
+        }
+    }
+
+    let (day, month, year, time, zone) = match tokens.as_slice() {
+        [day, month, year, time, zone, ..] => (*day, *month, *year, *time, Some(*zone)),
+        [day, month, year, time] => (*day, *month, *year, *time, None),
+        _ => return None,
+    };
+
+    let day: i64 = day.parse().ok()?;
+    let month = month.get(..3)?;
+    let month = months.iter().position(|m| m.eq_ignore_ascii_case(month))? as i64 + 1;
+    let year = match (year.len(), year.parse::<i64>().ok()?) {
+        (2, y) if y < 50 => y + 2000,
+        (2 | 3, y) => y + 1900,
+        (_, y) => y,
+    };
+    if !(1..=31).contains(&day) {
+        return None;
+    }
+
+    let mut hms = time.split(':').map(|n| n.parse::<i64>().ok());
+    let (hour, minute, second) = (hms.next()??, hms.next()??, hms.next().unwrap_or(Some(0))?);
+
+    Some(
+        days(year, month, day) * 86400 + hour * 3600 + minute * 60 + second
+            - zone.map_or(0, offset),
+    )
+}
+
+/// The offset of a zone from UTC, in seconds.
+fn offset(zone: &str) -> i64 {
+    let hours = match zone.to_ascii_uppercase().as_str() {
+        "UT" | "UTC" | "GMT" | "Z" => 0,
+        "EDT" => -4,
+        "EST" | "CDT" => -5,
+        "CST" | "MDT" => -6,
+        "MST" | "PDT" => -7,
+        "PST" => -8,
+        _ => {
+            let sign = match zone.chars().next() {
+                Some('+') => 1,
+                Some('-') => -1,
+                _ => return 0,
+            };
+            return match zone[1..].parse::<i64>() {
+                Ok(n) if zone.len() == 5 => sign * (n / 100 * 3600 + n % 100 * 60),
+                _ => 0,
+            };
+        }
+    };
+
+    hours * 3600
+}
+
+/// Days from 1970-01-01 to a date of the proleptic Gregorian calendar.
+fn days(year: i64, month: i64, day: i64) -> i64 {
+    let year = if month <= 2 { year - 1 } else { year };
+    let era = year.div_euclid(400);
+    let yoe = year - era * 400;
+    let doy = (153 * ((month + 9) % 12) + 2) / 5 + day - 1;
+    let doe = yoe * 365 + yoe / 4 - yoe / 100 + doy;
+
+    era * 146097 + doe - 719468
+}
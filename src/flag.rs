@@ -0,0 +1,120 @@
+use std::{borrow::Cow, fmt, str::FromStr};
+
+use futures_lite::io;
+
+use crate::read::TY;
+
+/// A message flag, either one of the system flags defined by RFC 3501 or a
+/// keyword.
+#[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub enum Flag {
+    Seen,
+    Answered,
+    Flagged,
+    Deleted,
+    Draft,
+    Recent,
+    Keyword(String),
+    /// `\*` in PERMANENTFLAGS, meaning new keywords can be created.
+    Wildcard,
+}
+
+impl Flag {
+    /// Reports whether a client can store the flag on a message. `\Recent`
+    /// is only set by the server, and `\*` is not a flag at all
+    /// (RFC 3501 section 2.3.2).
+    pub fn Storable(&self) -> bool {
+        !matches!(self, Flag::Recent | Flag::Wildcard)
+    }
+
+    /// Fails with a `FlagError` for the first flag that can't be stored.
+    pub fn CheckStorable(flags: &[Flag]) -> io::Result<()> {
+        match flags.iter().find(|f| !f.Storable()) {
+            Some(flag) => Err(io::Error::new(
+                io::ErrorKind::InvalidInput,
+                FlagError { Flag: flag.clone() },
+            )),
+            None => Ok(()),
+        }
+    }
+
+    /// Parses the flags of a list such as `(\Seen \Deleted $Forwarded)`.
+    pub fn ParseList(fields: &[TY<'_>]) -> Cow<'static, [Flag]> {
+        fields
+            .iter()
+            .filter_map(TY::AsStr)
+            .filter_map(|f| f.parse().ok())
+            .collect()
+    }
+}
+
+impl fmt::Display for Flag {
+    fn fmt(&self, fmt: &mut fmt::Formatter) -> fmt::Result {
+        fmt.write_str(match self {
+            Flag::Seen => "\\Seen",
+            Flag::Answered => "\\Answered",
+            Flag::Flagged => "\\Flagged",
+            Flag::Deleted => "\\Deleted",
+            Flag::Draft => "\\Draft",
+            Flag::Recent => "\\Recent",
+            Flag::Wildcard => "\\*",
+            Flag::Keyword(k) => k,
+        })
+    }
+}
+
+impl FromStr for Flag {
+    type Err = io::Error;
+
+    fn from_str(s: &str) -> io::Result<Flag> {
+        if s.is_empty() {
+            return Err(io::Error::new(io::ErrorKind::Other, "flag is empty"));
+        }
+
+        // System flags are case-insensitive
+        Ok(match s.to_ascii_lowercase().as_str() {
+            "\\seen" => Flag::Seen,
+            "\\answered" => Flag::Answered,
+            "\\flagged" => Flag::Flagged,
+            "\\deleted" => Flag::Deleted,
+            "\\draft" => Flag::Draft,
+            "\\recent" => Flag::Recent,
+            "\\*" => Flag::Wildcard,
+            _ => Flag::Keyword(s.to_string()),
+        })
+    }
+}
+
+/// A flag passed to STORE that a client can't store, see `Flag::Storable`.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct FlagError {
+    pub Flag: Flag,
+}
+
+impl FlagError {
+    /// Returns the `FlagError` inside an error from `Flag::CheckStorable`,
+    /// if any.
+    pub fn From(err: &io::Error) -> Option<&FlagError> {
+        err.get_ref().and_then(|e| e.downcast_ref::<FlagError>())
+    }
+}
+
+impl fmt::Display for FlagError {
+    fn fmt(&self, fmt: &mut fmt::Formatter) -> fmt::Result {
+        write!(fmt, "flag {} can't be stored", self.Flag)
+    }
+}
+
+impl std::error::Error for FlagError {}
+
+/// Formats flags as a parenthesized list suitable for a command argument.
+pub fn FormatFlags(flags: &[Flag]) -> String {
+    format!(
+        "({})",
+        flags
+            .iter()
+            .map(Flag::to_string)
+            .collect::<Vec<_>>()
+            .join(" ")
+    )
+}
@@ -0,0 +1,109 @@
+//! Decoding of header values.
+
+/// Decodes the RFC 2047 encoded words of a header value, such as
+/// `=?UTF-8?Q?Caf=C3=A9?=`. Whitespace between two encoded words is
+/// dropped. Words in a charset other than UTF-8, US-ASCII or ISO-8859-1,
+/// or that can't be decoded, are kept as is.
+pub fn DecodeWords(value: &str) -> String {
+    let mut out = String::with_capacity(value.len());
+    let mut rest = value;
+    let mut afterWord = false;
+
+    while let Some(start) = rest.find("=?") {
+        let (before, word) = rest.split_at(start);
+        let decoded = encodedWord(word);
+
+        if !(afterWord && decoded.is_some() && before.trim().is_empty()) {
+            out.push_str(before);
+        }
+
+        match decoded {
+            Some((text, len)) => {
+                out.push_str(&text);
+                rest = &word[len..];
+                afterWord = true;
+            }
+            None => {
+                out.push_str("=?");
+                rest = &word[2..];
+                afterWord = false;
+            }
+        }
+    }
+    out.push_str(rest);
+
+    out
+}
+
+/// Decodes the encoded word at the start of `s`, and returns it with its
+/// length.
+fn encodedWord(s: &str) -> Option<(String, usize)> {
+    let mut parts = s[2..].splitn(3, '?');
+    let (charset, encoding, text) = (parts.next()?, parts.next()?, parts.next()?);
+    let end = text.find("?=")?;
+    let text = &text[..end];
+    if text.contains(char::is_whitespace) {
+        return None;
+    }
+
+    let bytes = match encoding {
+        "B" | "b" => base64(text)?,
+        "Q" | "q" => quoted(text)?,
+        _ => return None,
+    };
+
+    let len = 2 + charset.len() + 1 + encoding.len() + 1 + end + 2;
+
+    // RFC 2231 adds a language after a '*'
+    let decoded = match charset.split('*').next()?.to_ascii_lowercase().as_str() {
+        "utf-8" | "us-ascii" => String::from_utf8(bytes).ok()?,
+        "iso-8859-1" | "latin1" => bytes.into_iter().map(char::from).collect(),
+        _ => return None,
+    };
+
+    Some((decoded, len))
+}
+
+fn base64(text: &str) -> Option<Vec<u8>> {
+    let mut out = Vec::new();
+    let (mut bits, mut nbits) = (0u32, 0);
+
+    for c in text.trim_end_matches('=').bytes() {
+        let v = match c {
+            b'A'..=b'Z' => c - b'A',
+            b'a'..=b'z' => c - b'a' + 26,
+            b'0'..=b'9' => c - b'0' + 52,
+            b'+' => 62,
+            b'/' => 63,
+            _ => return None,
+        };
+        bits = bits << 6 | v as u32;
+        nbits += 6;
+
+        if nbits >= 8 {
+            nbits -= 8;
+            out.push((bits >> nbits) as u8);
+            bits &= (1 << nbits) - 1;
+        }
+    }
+
+    Some(out)
+}
+
+fn quoted(text: &str) -> Option<Vec<u8>> {
+    let mut out = Vec::new();
+    let mut bytes = text.bytes();
+
+    while let Some(c) = bytes.next() {
+        match c {
+            b'_' => out.push(b' '),
+            b'=' => {
+                let hex = [bytes.next()?, bytes.next()?];
+                out.push(u8::from_str_radix(std::str::from_utf8(&hex).ok()?, 16).ok()?);
+            }
+            _ => out.push(c),
+        }
+    }
+
+    Some(out)
+}
@@ -18,11 +18,46 @@ pub use read::Reader;
 pub mod response;
 pub use response::Resp;
 
+pub mod flag;
+pub use flag::Flag;
+
+pub mod seqset;
+pub use seqset::SequenceSet;
+
+pub mod rights;
+pub use rights::Rights;
+
 pub mod commands;
+pub mod date;
+pub mod header;
 pub mod responses;
+pub mod sorting;
+pub mod threading;
+pub mod utf7;
+
+#[cfg(test)]
+mod client_tests;
+
+#[cfg(test)]
+mod commands_tests;
 
 #[cfg(test)]
 mod read_tests;
 
 #[cfg(test)]
 mod response_tests;
+
+#[cfg(test)]
+mod rights_tests;
+
+#[cfg(test)]
+mod seqset_tests;
+
+#[cfg(test)]
+mod sorting_tests;
+
+#[cfg(test)]
+mod threading_tests;
+
+#[cfg(test)]
+mod utf7_tests;
@@ -17,7 +17,40 @@ use super::{
 #[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord)]
 pub enum TY<'a> {
     Str(Cow<'a, str>),
-    List(Cow<'a, [Cow<'a, str>]>),
+    List(Cow<'a, [TY<'a>]>),
+    Nil,
+}
+
+impl<'a> TY<'a> {
+    pub fn AsStr(&self) -> Option<&str> {
+        match self {
+            TY::Str(s) => Some(s.as_ref()),
+            _ => None,
+        }
+    }
+
+    pub fn AsList(&self) -> Option<&[TY<'a>]> {
+        match self {
+            TY::List(l) => Some(l.as_ref()),
+            _ => None,
+        }
+    }
+
+    pub fn IsNil(&self) -> bool {
+        matches!(self, TY::Nil)
+    }
+
+    pub fn Parse<N: std::str::FromStr>(&self) -> Option<N> {
+        self.AsStr().and_then(|s| s.parse::<N>().ok())
+    }
+
+    pub fn IntoOwned<'b>(self) -> TY<'b> {
+        match self {
+            TY::Str(s) => TY::Str(Cow::Owned(s.into_owned())),
+            TY::List(l) => TY::List(l.into_owned().into_iter().map(TY::IntoOwned).collect()),
+            TY::Nil => TY::Nil,
+        }
+    }
 }
 
 #[derive(Debug, Default)]
@@ -26,26 +59,60 @@ pub struct Reader<T: BorrowMut<T> + Unpin> {
     brackets: u8,
     inRespCode: bool,
     last: (char, bool),
+    /// A byte read past the end of an invalid UTF-8 sequence.
+    peeked: Option<u8>,
     MaxLiteralSize: u32,
 }
 
 impl<'r, T: io::AsyncReadExt + Unpin + Send> Reader<T> {
+    /// Reads a UTF-8 encoded character. Invalid sequences, such as 8-bit
+    /// text in a legacy charset, are read as U+FFFD.
     pub async fn ReadRune(&mut self) -> io::Result<char> {
         if self.last.1 {
             self.last.1 = false;
-            Ok(self.last.0.into())
-        } else {
-            let buf = &mut [0u8; 1];
-            match self.stream.read(buf).await? {
-                1 => {
-                    let c = *buf
-                        .get(0)
-                        .ok_or(io::Error::from(io::ErrorKind::InvalidData))?;
-                    self.last = (c.into(), false);
-                    Ok(c.into())
-                }
-                _ => Err(io::Error::from(io::ErrorKind::InvalidData)),
+            return Ok(self.last.0);
+        }
+
+        let first = self.readByte().await?;
+        let len = match first {
+            0x00..=0x7f => 1,
+            0xc2..=0xdf => 2,
+            0xe0..=0xef => 3,
+            0xf0..=0xf4 => 4,
+            _ => 0,
+        };
+
+        let mut buf = [first, 0, 0, 0];
+        let mut valid = len > 0;
+        for i in 1..len {
+            let b = self.readByte().await?;
+            if b & 0xc0 != 0x80 {
+                // Not a continuation byte, it starts the next character
+                self.peeked = Some(b);
+                valid = false;
+                break;
             }
+            buf[i] = b;
+        }
+
+        let c = match std::str::from_utf8(&buf[..len]) {
+            Ok(s) if valid => s.chars().next().unwrap_or(char::REPLACEMENT_CHARACTER),
+            _ => char::REPLACEMENT_CHARACTER,
+        };
+
+        self.last = (c, false);
+        Ok(c)
+    }
+
+    async fn readByte(&mut self) -> io::Result<u8> {
+        if let Some(b) = self.peeked.take() {
+            return Ok(b);
+        }
+
+        let buf = &mut [0u8; 1];
+        match self.stream.read(buf).await? {
+            1 => Ok(buf[0]),
+            _ => Err(io::Error::from(io::ErrorKind::InvalidData)),
         }
     }
 
@@ -146,10 +213,6 @@ impl<'r, T: io::AsyncReadExt + Unpin + Send> Reader<T> {
 
         self.UnReadRune().await;
 
-        if atom.eq("NIL") {
-            atom.to_mut().clear();
-        }
-
         Ok(atom)
     }
 
@@ -184,12 +247,16 @@ impl<'r, T: io::AsyncReadExt + Unpin + Send> Reader<T> {
 
         self.ReadCrlf().await?;
 
-        let mut s = Cow::<'_, str>::default();
-        while s.len() < n as usize {
-            s.to_mut().push(self.ReadRune().await?.into());
+        // The length counts bytes, not characters
+        let mut buf = Vec::with_capacity(n as usize);
+        for _ in 0..n {
+            buf.push(self.readByte().await?);
         }
 
-        Ok(s)
+        Ok(match String::from_utf8(buf) {
+            Ok(s) => Cow::Owned(s),
+            Err(e) => Cow::Owned(String::from_utf8_lossy(e.as_bytes()).into_owned()),
+        })
     }
 
     pub async fn ReadQuotedString<'b>(&mut self) -> io::Result<Cow<'b, str>> {
@@ -265,6 +332,9 @@ impl<'r, T: io::AsyncReadExt + Unpin + Send> Reader<T> {
                 }
                 _ => {
                     field = self.ReadAtom().await?;
+                    if field.eq_ignore_ascii_case("NIL") {
+                        field.to_mut().clear();
+                    }
                 }
             }
 
@@ -354,7 +424,12 @@ impl<'r, T: io::AsyncReadExt + Unpin + Send> Reader<T> {
 
         let codeStr = match &fields[0] {
             TY::Str(s) => s,
-            TY::List(l) => &l[0],
+            TY::List(_) | TY::Nil => {
+                return Err(std::io::Error::new(
+                    std::io::ErrorKind::Other,
+                    "response code doesn't start with a string atom",
+                ));
+            }
         };
         if codeStr.chars().all(char::is_numeric) {
             return Err(std::io::Error::new(
@@ -424,26 +499,32 @@ impl<'r, T: io::AsyncReadExt + Unpin + Send> Reader<T> {
                         .push(TY::Str(self.ReadQuotedString().await?));
                 }
                 listStart => {
-                    let mut list = Cow::<'b, [Cow<'b, str>]>::default();
-                    for e in self.ReadList2().await?.iter() {
-                        match e {
-                            TY::Str(s) => list.to_mut().push(s.to_owned()),
-                            TY::List(l) => {
-                                for s in l.iter() {
-                                    list.to_mut().push(s.to_owned());
-                                }
-                            }
-                        }
-                    }
-
-                    fields.to_mut().push(TY::List(list));
+                    fields.to_mut().push(TY::List(self.ReadList2().await?));
                 }
                 listEnd => ok = false,
                 cr => {
                     return Ok(fields);
                 }
+                '~' => {
+                    // A literal8, or an atom starting with a tilde
+                    self.ReadRune().await?;
+                    let next = self.ReadRune().await?;
+                    self.UnReadRune().await;
+
+                    if next == literalStart {
+                        fields.to_mut().push(TY::Str(self.ReadLiteral().await?));
+                    } else {
+                        let atom = self.ReadAtom().await?;
+                        fields.to_mut().push(TY::Str(format!("~{}", atom).into()));
+                    }
+                }
                 _ => {
-                    fields.to_mut().push(TY::Str(self.ReadAtom().await?));
+                    let atom = self.ReadAtom().await?;
+                    fields.to_mut().push(if atom.eq_ignore_ascii_case("NIL") {
+                        TY::Nil
+                    } else {
+                        TY::Str(atom)
+                    });
                 }
             }
 
@@ -513,6 +594,7 @@ impl<'r, T: Unpin> From<T> for Reader<T> {
             brackets: 0,
             inRespCode: false,
             last: ('\0', false),
+            peeked: None,
             MaxLiteralSize: 0,
         }
     }
@@ -1,4 +1,4 @@
-use crate::Reader;
+use crate::{read::TY, Reader};
 use futures_lite::AsyncReadExt;
 
 #[test]
@@ -50,6 +50,11 @@ fn TestReader_ReadLiteral() {
         let mut r = Reader::from(b"{7}\r\nabcdefg".bytes());
         debug_assert!(r.ReadLiteral().await.map_or(false, |a| a == "abcdefg"));
 
+        // The length is in bytes
+        let mut r = Reader::from("{9}\r\nEntwürfe)".as_bytes().bytes());
+        debug_assert!(r.ReadLiteral().await.map_or(false, |a| a == "Entwürfe"));
+        debug_assert!(r.ReadRune().await.map_or(false, |c| c == ')'));
+
         debug_assert!(Reader::from(b"".bytes()).ReadLiteral().await.is_err());
 
         debug_assert!(Reader::from(b"[7}\r\nabcdefg".bytes())
@@ -158,6 +163,20 @@ fn TestReader_ReadFields2() {
     smol::block_on(async {
         let mut r = Reader::from(b"(field1 \"field2\") field1\r\n".bytes());
         dbg!(r.ReadFields2().await);
+
+        let mut r = Reader::from(b"(FLAGS (\\Seen) UID 4) (1 (2)(3 4))\r\n".bytes());
+        let s = |s: &'static str| TY::Str(s.into());
+        let l = |l: Vec<TY<'static>>| TY::List(l.into());
+        debug_assert!(r.ReadFields2().await.map_or(false, |a| a.as_ref()
+            == [
+                l(vec![s("FLAGS"), l(vec![s("\\Seen")]), s("UID"), s("4")]),
+                l(vec![s("1"), l(vec![s("2")]), l(vec![s("3"), s("4")])]),
+            ]));
+
+        // NIL is distinct from the empty string
+        let mut r = Reader::from(b"NIL \"\" (nil \"NIL\")\r\n".bytes());
+        debug_assert!(r.ReadFields2().await.map_or(false, |a| a.as_ref()
+            == [TY::Nil, s(""), l(vec![TY::Nil, s("NIL")])]));
     })
 }
 
@@ -274,3 +293,20 @@ fn TestReader_ReadInfo() {
             .is_err());
     })
 }
+
+#[test]
+fn TestReader_ReadRune_Utf8() {
+    smol::block_on(async {
+        let mut r = Reader::from("\"受信箱 📧\"\r\n".as_bytes().bytes());
+        debug_assert!(r
+            .ReadQuotedString()
+            .await
+            .map_or(false, |a| a == "受信箱 📧"));
+
+        // Invalid sequences don't swallow the next character
+        let mut r = Reader::from(b"\xfc\xc3\"".bytes());
+        debug_assert!(r.ReadRune().await.map_or(false, |c| c == '\u{fffd}'));
+        debug_assert!(r.ReadRune().await.map_or(false, |c| c == '\u{fffd}'));
+        debug_assert!(r.ReadRune().await.map_or(false, |c| c == '"'));
+    })
+}
@@ -4,7 +4,7 @@ use std::{
     ops::Deref,
 };
 
-use crate::read::TY;
+use crate::{read::TY, SequenceSet};
 
 use super::Reader;
 use futures_lite::io;
@@ -22,6 +22,22 @@ pub enum Resp<'a> {
     DataResp(DataResp<'a>),
 }
 
+impl<'a> Resp<'a> {
+    /// Reports whether this is an OK status response.
+    pub fn IsOk(&self) -> bool {
+        matches!(self, Resp::StatResp(StatusResp { Type, .. }) if Type == "OK")
+    }
+
+    /// See `StatusResp::Check`. Responses other than status responses
+    /// are never errors.
+    pub fn Check(&self) -> io::Result<()> {
+        match self {
+            Resp::StatResp(status) => status.Check(),
+            _ => Ok(()),
+        }
+    }
+}
+
 #[derive(Debug, Clone, Default, PartialEq, Eq, PartialOrd, Ord)]
 pub struct StatusResp<'a> {
     pub Tag: Cow<'a, str>,
@@ -31,6 +47,175 @@ pub struct StatusResp<'a> {
     pub Info: Cow<'a, str>,
 }
 
+impl<'a> StatusResp<'a> {
+    /// Parses the `[APPENDUID uidvalidity uid-set]` response code (RFC 4315).
+    pub fn AppendUid(&self) -> Option<AppendUid> {
+        match (self.Code.as_ref(), self.Arguments.as_ref()) {
+            ("APPENDUID", [validity, uids]) => Some(AppendUid {
+                UidValidity: validity.Parse()?,
+                Uids: uids.Parse()?,
+            }),
+            _ => None,
+        }
+    }
+
+    /// Parses the `[COPYUID uidvalidity src-uids dst-uids]` response code
+    /// (RFC 4315).
+    pub fn CopyUid(&self) -> Option<CopyUid> {
+        match (self.Code.as_ref(), self.Arguments.as_ref()) {
+            ("COPYUID", [validity, src, dst]) => Some(CopyUid {
+                UidValidity: validity.Parse()?,
+                SourceUids: src.Parse()?,
+                DestUids: dst.Parse()?,
+            }),
+            _ => None,
+        }
+    }
+}
+
+impl<'a> StatusResp<'a> {
+    /// Parses the `[MODIFIED set]` response code of a conditional STORE
+    /// (RFC 7162): the messages that were not stored, as they changed. Sent
+    /// with OK when only some messages failed.
+    pub fn Modified(&self) -> Option<SequenceSet> {
+        match (self.Code.as_ref(), self.Arguments.as_ref()) {
+            ("MODIFIED", [set]) => set.Parse(),
+            _ => None,
+        }
+    }
+
+    /// Parses the `[METADATA LONGENTRIES n]` response code of GETMETADATA
+    /// (RFC 5464): the size of the largest value left out by `MaxSize`.
+    pub fn LongEntries(&self) -> Option<u32> {
+        match (self.Code.as_ref(), self.Arguments.as_ref()) {
+            ("METADATA", [TY::Str(kind), size]) if kind.eq_ignore_ascii_case("LONGENTRIES") => {
+                size.Parse()
+            }
+            _ => None,
+        }
+    }
+}
+
+impl<'a> StatusResp<'a> {
+    /// Turns a NO or BAD response into a `StatusError`, wrapped in an
+    /// `io::Error`.
+    pub fn Check(&self) -> io::Result<()> {
+        match self.Type.as_ref() {
+            "NO" | "BAD" => Err(io::Error::new(
+                io::ErrorKind::Other,
+                StatusError {
+                    Type: self.Type.to_string(),
+                    Code: ErrorCode::Parse(self),
+                    Info: self.Info.to_string(),
+                },
+            )),
+            _ => Ok(()),
+        }
+    }
+}
+
+/// Response code of a failed command, for the codes callers act on.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum ErrorCode {
+    None,
+    /// The target mailbox does not exist and may be created.
+    TryCreate,
+    /// A CATENATE URL could not be resolved (RFC 4469).
+    BadUrl(String),
+    /// The message would be too big to be stored (RFC 4469).
+    TooBig,
+    /// The command would exceed a quota, e.g. an APPEND or COPY into a
+    /// full mailbox (RFC 9208).
+    OverQuota,
+    /// SETMETADATA would exceed the number of entries allowed (RFC 5464).
+    MetadataTooMany,
+    /// A SETMETADATA value is larger than the given size (RFC 5464).
+    MetadataMaxSize(u32),
+    /// The server doesn't support private annotations (RFC 5464).
+    MetadataNoPrivate,
+    /// A conditional STORE failed for these messages, as they changed since
+    /// the given mod-sequence (RFC 7162).
+    Modified(SequenceSet),
+    Other(String),
+}
+
+impl ErrorCode {
+    fn Parse(resp: &StatusResp<'_>) -> ErrorCode {
+        match resp.Code.as_ref() {
+            "" => ErrorCode::None,
+            "TRYCREATE" => ErrorCode::TryCreate,
+            "BADURL" => ErrorCode::BadUrl(
+                resp.Arguments
+                    .get(0)
+                    .and_then(TY::AsStr)
+                    .unwrap_or_default()
+                    .to_string(),
+            ),
+            "TOOBIG" => ErrorCode::TooBig,
+            "MODIFIED" => ErrorCode::Modified(resp.Modified().unwrap_or_default()),
+            "OVERQUOTA" => ErrorCode::OverQuota,
+            "METADATA" => {
+                let arg = |i: usize| resp.Arguments.get(i).and_then(TY::AsStr);
+                match arg(0).unwrap_or_default().to_uppercase().as_str() {
+                    "TOOMANY" => ErrorCode::MetadataTooMany,
+                    "MAXSIZE" => {
+                        ErrorCode::MetadataMaxSize(arg(1).and_then(|n| n.parse().ok()).unwrap_or(0))
+                    }
+                    "NOPRIVATE" => ErrorCode::MetadataNoPrivate,
+                    _ => ErrorCode::Other("METADATA".to_string()),
+                }
+            }
+            code => ErrorCode::Other(code.to_string()),
+        }
+    }
+}
+
+/// Error for a command that completed with NO or BAD.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct StatusError {
+    pub Type: String,
+    pub Code: ErrorCode,
+    pub Info: String,
+}
+
+impl StatusError {
+    /// Returns the `StatusError` inside an error from `Check`, if any.
+    pub fn From(err: &io::Error) -> Option<&StatusError> {
+        err.get_ref().and_then(|e| e.downcast_ref::<StatusError>())
+    }
+}
+
+impl std::fmt::Display for StatusError {
+    fn fmt(&self, fmt: &mut std::fmt::Formatter) -> std::fmt::Result {
+        write!(fmt, "{} {}", self.Type, self.Info)
+    }
+}
+
+impl std::error::Error for StatusError {}
+
+/// UIDs assigned to appended messages.
+#[derive(Debug, Default, Clone, PartialEq, Eq)]
+pub struct AppendUid {
+    pub UidValidity: u32,
+    pub Uids: SequenceSet,
+}
+
+/// UIDs of copied or moved messages in the source and destination
+/// mailboxes. Both sets list the messages in the same order.
+#[derive(Debug, Default, Clone, PartialEq, Eq)]
+pub struct CopyUid {
+    pub UidValidity: u32,
+    pub SourceUids: SequenceSet,
+    pub DestUids: SequenceSet,
+}
+
+impl CopyUid {
+    /// Pairs each source UID with the UID of its copy.
+    pub fn Pairs(&self) -> io::Result<impl Iterator<Item = (u32, u32)> + '_> {
+        Ok(self.SourceUids.Ids()?.zip(self.DestUids.Ids()?))
+    }
+}
+
 #[derive(Debug, Default, Clone, PartialEq, Eq, PartialOrd, Ord)]
 pub struct ContinuationReq<'a> {
     pub Info: Cow<'a, str>,
@@ -1,9 +1,12 @@
 use std::borrow::Cow;
 
 use crate::{
+    commands::QuotaResource,
+    flag::FlagError,
     read::TY,
-    response::{DataResp, Resp},
-    Reader,
+    response::{AppendUid, DataResp, ErrorCode, Resp, StatusError},
+    responses::{self, fetch, resync, Address, Handler, MailboxAttribute, Message, ThreadNode},
+    Flag, Reader, Rights,
 };
 use futures_lite::AsyncReadExt;
 
@@ -146,3 +149,752 @@ fn TestParseNamedResp() {
             });
     })
 }
+
+#[test]
+fn TestParseFetch() {
+    smol::block_on(async {
+        let resp = Reader::from(b"* 12 FETCH (FLAGS (\\Seen $Forwarded) UID 4827)\r\n".bytes())
+            .ReadResp()
+            .await
+            .unwrap();
+
+        debug_assert!(
+            fetch::ParseFetch(&resp).await.unwrap()
+                == Some(Message {
+                    SeqNum: 12,
+                    Uid: Some(4827),
+                    Flags: Some(Cow::Owned(vec![
+                        Flag::Seen,
+                        Flag::Keyword("$Forwarded".into())
+                    ])),
+                    ..Default::default()
+                })
+        );
+
+        let resp = Reader::from(b"* 3 EXISTS\r\n".bytes())
+            .ReadResp()
+            .await
+            .unwrap();
+        debug_assert!(fetch::ParseFetch(&resp).await.unwrap().is_none());
+    })
+}
+
+#[test]
+fn TestFlag_Parse() {
+    debug_assert!("\\SEEN".parse::<Flag>().unwrap() == Flag::Seen);
+    debug_assert!("\\*".parse::<Flag>().unwrap() == Flag::Wildcard);
+    debug_assert!("$Junk".parse::<Flag>().unwrap() == Flag::Keyword("$Junk".into()));
+    debug_assert!(Flag::Deleted.to_string() == "\\Deleted");
+}
+
+#[test]
+fn TestFlag_Storable() {
+    debug_assert!(Flag::CheckStorable(&[Flag::Seen, Flag::Keyword("$Junk".into())]).is_ok());
+
+    let err = Flag::CheckStorable(&[Flag::Seen, Flag::Recent, Flag::Wildcard]).unwrap_err();
+    debug_assert!(FlagError::From(&err) == Some(&FlagError { Flag: Flag::Recent }));
+    debug_assert!(!Flag::Wildcard.Storable());
+}
+
+#[test]
+fn TestStatusResp_UidPlus() {
+    smol::block_on(async {
+        let resp = Reader::from(b"A003 OK [APPENDUID 38505 3955] APPEND completed\r\n".bytes())
+            .ReadResp()
+            .await
+            .unwrap();
+        debug_assert!(match resp {
+            Resp::StatResp(status) =>
+                status.AppendUid()
+                    == Some(AppendUid {
+                        UidValidity: 38505,
+                        Uids: 3955.into(),
+                    }),
+            _ => false,
+        });
+
+        let resp = Reader::from(b"A004 OK [COPYUID 38505 304,319:320 3956:3958] Done\r\n".bytes())
+            .ReadResp()
+            .await
+            .unwrap();
+        debug_assert!(match resp {
+            Resp::StatResp(status) => status.CopyUid().map_or(false, |c| c.UidValidity == 38505
+                && c.Pairs().unwrap().collect::<Vec<_>>()
+                    == [(304, 3956), (319, 3957), (320, 3958)]),
+            _ => false,
+        });
+
+        let resp = Reader::from(b"A005 OK [READ-ONLY] Done\r\n".bytes())
+            .ReadResp()
+            .await
+            .unwrap();
+        debug_assert!(match resp {
+            Resp::StatResp(status) => status.AppendUid().is_none() && status.CopyUid().is_none(),
+            _ => false,
+        });
+    })
+}
+
+#[test]
+fn TestStatusResp_Check() {
+    smol::block_on(async {
+        let resp = Reader::from(b"A003 NO [TRYCREATE] No such mailbox\r\n".bytes())
+            .ReadResp()
+            .await
+            .unwrap();
+
+        let err = resp.Check().unwrap_err();
+        debug_assert!(StatusError::From(&err).map_or(false, |e| e.Type == "NO"
+            && e.Code == ErrorCode::TryCreate
+            && e.Info == "No such mailbox"));
+
+        let resp = Reader::from(
+            b"A003 NO [BADURL \"/INBOX;UIDVALIDITY=1/;UID=5\"] No such message\r\n".bytes(),
+        )
+        .ReadResp()
+        .await
+        .unwrap();
+        debug_assert!(
+            StatusError::From(&resp.Check().unwrap_err()).map_or(false, |e| e.Code
+                == ErrorCode::BadUrl("/INBOX;UIDVALIDITY=1/;UID=5".into()))
+        );
+
+        let resp = Reader::from(b"A003 OK done\r\n".bytes())
+            .ReadResp()
+            .await
+            .unwrap();
+        debug_assert!(resp.Check().is_ok());
+    })
+}
+
+async fn handleAll<'s, H: Handler<'s>>(h: &mut H, input: &'static [u8]) {
+    let mut r = Reader::from(input.bytes());
+    while let Ok(mut resp) = r.ReadResp().await {
+        h.Handle(&mut resp).await.unwrap();
+    }
+}
+
+#[test]
+fn TestList_Cache() {
+    smol::block_on(async {
+        let mut list = responses::List::default();
+        handleAll(
+            &mut list,
+            b"* LIST (\\HasChildren) \"/\" Work\r\n\
+              * LIST (\\HasNoChildren) \"/\" Work/Old\r\n\
+              * LIST (\\HasNoChildren) \"/\" Workshop\r\n\
+              * LIST () \"/\" INBOX\r\n",
+        )
+        .await;
+
+        let mut lsub = responses::List::default();
+        handleAll(&mut lsub, b"* LSUB () \"/\" Work\r\n").await;
+
+        let mut cache = responses::List::default();
+        cache.Merge(&list);
+        cache.MergeSubscribed(&lsub);
+        debug_assert!(cache.Get("Work").map_or(false, |m| m.Subscribed()));
+        debug_assert!(cache.Delimiter() == Some("/"));
+
+        // Merging LIST again keeps the subscription
+        cache.Merge(&list);
+        debug_assert!(cache.Get("Work").map_or(false, |m| m.Subscribed()));
+
+        cache.Renamed("Work", "Job");
+        debug_assert!(cache.Get("Work").is_none() && cache.Get("Work/Old").is_none());
+        debug_assert!(cache.Get("Job").is_some() && cache.Get("Job/Old").is_some());
+        debug_assert!(cache.Get("Workshop").is_some());
+
+        cache.Created("Projects/", &[]);
+        debug_assert!(cache.Get("Projects").is_some());
+
+        cache.Deleted("Job");
+        debug_assert!(cache.Get("Job").is_none() && cache.Get("Job/Old").is_some());
+
+        cache.Renamed("INBOX", "Old Inbox");
+        debug_assert!(cache.Get("INBOX").is_some() && cache.Get("Old Inbox").is_some());
+
+        cache.SetSubscribed("Workshop", true);
+        cache.SetSubscribed("Workshop", false);
+        debug_assert!(cache.Get("Workshop").map_or(false, |m| !m.Subscribed()));
+
+        // INBOX is case-insensitive, other names are not
+        debug_assert!(cache.Get("inbox").is_some() && cache.Get("workshop").is_none());
+        let mut inbox = responses::List::default();
+        handleAll(&mut inbox, b"* LIST (\\Marked) \"/\" Inbox\r\n").await;
+        cache.Merge(&inbox);
+        debug_assert!(
+            cache
+                .Mailboxes
+                .iter()
+                .filter(|m| m.Name.eq_ignore_ascii_case("inbox"))
+                .count()
+                == 1
+        );
+
+        // A LIST of everything drops the mailboxes deleted elsewhere
+        let mut all = responses::List::default();
+        handleAll(
+            &mut all,
+            b"* LIST () \"/\" INBOX\r\n\
+              * LIST () \"/\" Workshop\r\n",
+        )
+        .await;
+        let mut projects = cache.clone();
+        projects.Replace("Projects/", &responses::List::default());
+        debug_assert!(projects.Mailboxes.len() == cache.Mailboxes.len());
+        cache.Replace("", &all);
+        debug_assert!(
+            cache
+                .Mailboxes
+                .iter()
+                .map(|m| m.Name.as_ref())
+                .collect::<Vec<_>>()
+                == ["Workshop", "INBOX"]
+        );
+    })
+}
+
+#[test]
+fn TestParseStatus() {
+    smol::block_on(async {
+        let mut status = responses::Status::default();
+        handleAll(
+            &mut status,
+            b"* STATUS \"Sent Items\" (MESSAGES 231 UIDNEXT 44292 UNSEEN 3 HIGHESTMODSEQ 7011231777)\r\n",
+        )
+        .await;
+
+        debug_assert!(
+            status.Mailbox
+                == responses::StatusInfo {
+                    Name: "Sent Items".into(),
+                    Messages: Some(231),
+                    UidNext: Some(44292),
+                    Unseen: Some(3),
+                    HighestModSeq: Some(7011231777),
+                    ..Default::default()
+                }
+        );
+
+        let mut status = responses::Status::default();
+        handleAll(&mut status, b"* STATUS 2024 (MESSAGES 2)\r\n").await;
+        debug_assert!(status.Mailbox.Name == "2024" && status.Mailbox.Messages == Some(2));
+    })
+}
+
+#[test]
+fn TestList_Extended() {
+    smol::block_on(async {
+        let mut list = responses::List::default();
+        handleAll(
+            &mut list,
+            b"* LIST () \"/\" Foo (\"CHILDINFO\" (\"SUBSCRIBED\"))\r\n\
+              * LIST () \"/\" Bar (\"OLDNAME\" (\"Baz\"))\r\n\
+              * STATUS Bar (MESSAGES 17 UNSEEN 16)\r\n",
+        )
+        .await;
+
+        let foo = list.Get("Foo").unwrap();
+        debug_assert!(foo.ChildInfo.as_ref() == ["SUBSCRIBED"] && foo.Status.is_none());
+
+        let bar = list.Get("Bar").unwrap();
+        debug_assert!(bar.OldName.as_deref() == Some("Baz"));
+        debug_assert!(bar
+            .Status
+            .as_ref()
+            .map_or(false, |s| s.Messages == Some(17) && s.Unseen == Some(16)));
+
+        let mut list = responses::List::default();
+        handleAll(
+            &mut list,
+            b"* LIST () \"/\" Entw&APw-rfe (\"OLDNAME\" (\"Alte Entw&APw-rfe\"))\r\n",
+        )
+        .await;
+        let drafts = list.Get("Entwürfe").unwrap();
+        debug_assert!(drafts.OldName.as_deref() == Some("Alte Entwürfe"));
+
+        // Names are sent as UTF-8 once UTF8=ACCEPT is enabled
+        let mut list = responses::List {
+            Utf8: true,
+            ..Default::default()
+        };
+        handleAll(
+            &mut list,
+            b"* LIST () \"/\" Entw&APw-rfe (\"OLDNAME\" (\"Alte Entw&APw-rfe\"))\r\n\
+              * STATUS Entw&APw-rfe (MESSAGES 3)\r\n",
+        )
+        .await;
+        let mbox = list.Get("Entw&APw-rfe").unwrap();
+        debug_assert!(mbox.OldName.as_deref() == Some("Alte Entw&APw-rfe"));
+        debug_assert!(mbox
+            .Status
+            .as_ref()
+            .map_or(false, |s| s.Messages == Some(3)));
+    })
+}
+
+#[test]
+fn TestList_SpecialUse() {
+    smol::block_on(async {
+        let mut list = responses::List::default();
+        handleAll(
+            &mut list,
+            b"* LIST (\\HasNoChildren \\trash) \"/\" Bin\r\n\
+              * LIST (\\HasNoChildren) \"/\" \"Sent Items\"\r\n\
+              * LIST (\\X-Custom) \"/\" Other\r\n",
+        )
+        .await;
+
+        let bin = list.Get("Bin").unwrap();
+        debug_assert!(
+            bin.Attributes.as_ref() == [MailboxAttribute::HasNoChildren, MailboxAttribute::Trash]
+        );
+        debug_assert!(
+            list.Get("Other").unwrap().Attributes.as_ref()
+                == [MailboxAttribute::Other("\\X-Custom".into())]
+        );
+
+        let find = |role| list.FindSpecialUse(&role).map(|m| m.Name.to_string());
+        debug_assert!(find(MailboxAttribute::Trash).as_deref() == Some("Bin"));
+        debug_assert!(find(MailboxAttribute::Sent).as_deref() == Some("Sent Items"));
+        debug_assert!(find(MailboxAttribute::Junk).is_none());
+    })
+}
+
+#[test]
+fn TestMailboxTree() {
+    smol::block_on(async {
+        let mut list = responses::List::default();
+        handleAll(
+            &mut list,
+            b"* LIST () \"/\" INBOX\r\n\
+              * LIST (\\HasChildren) \"/\" Work\r\n\
+              * LIST () \"/\" Work/2024/Q1\r\n\
+              * LIST (\\NonExistent) \"/\" Work/Old\r\n\
+              * LIST () \"/\" Work/Old/Done\r\n\
+              * LIST () \".\" Lists.rust\r\n\
+              * LIST () NIL Flat/Name\r\n",
+        )
+        .await;
+
+        let tree = list.Tree();
+        let roots: Vec<_> = tree.Roots.iter().map(|n| n.Name.as_str()).collect();
+        debug_assert!(roots == ["INBOX", "Work", "Lists", "Flat/Name"]);
+
+        let year = tree.Get("Work/2024").unwrap();
+        debug_assert!(year.IsSynthesized() && year.Children[0].Name == "Q1");
+        debug_assert!(tree.Get("Lists.rust").map_or(false, |n| !n.IsSynthesized()));
+        debug_assert!(tree.Get("Work/2025").is_none());
+        debug_assert!(tree.Parent("Work/2024/Q1").map(|n| n.Path.as_str()) == Some("Work/2024"));
+        debug_assert!(tree.Parent("Work").is_none());
+
+        let rename = tree.PlanRename("Work", "Job");
+        debug_assert!(
+            rename
+                == [
+                    ("Work".to_string(), "Job".to_string()),
+                    ("Work/2024/Q1".to_string(), "Job/2024/Q1".to_string()),
+                    ("Work/Old".to_string(), "Job/Old".to_string()),
+                    ("Work/Old/Done".to_string(), "Job/Old/Done".to_string()),
+                ]
+        );
+
+        debug_assert!(tree.PlanDelete("Work") == ["Work/Old/Done", "Work/2024/Q1", "Work"]);
+    })
+}
+
+#[test]
+fn TestEnable() {
+    smol::block_on(async {
+        let mut enable = responses::Enable::default();
+        handleAll(&mut enable, b"* ENABLED UTF8=ACCEPT\r\n* ENABLED\r\n").await;
+        debug_assert!(enable.Enabled.as_ref() == ["UTF8=ACCEPT"]);
+    })
+}
+
+#[test]
+fn TestNamespace() {
+    smol::block_on(async {
+        let mut ns = responses::Namespace::default();
+        handleAll(
+            &mut ns,
+            b"* NAMESPACE ((\"\" \"/\")) NIL ((\"#shared/\" \"/\")(\"#public\" NIL \"X-PARAM\" (\"FLAG1\" \"FLAG2\")))\r\n",
+        )
+        .await;
+
+        debug_assert!(
+            ns.Personal.as_ref()
+                == [responses::NamespaceDesc {
+                    Prefix: "".into(),
+                    Delimiter: Some("/".into()),
+                    ..Default::default()
+                }]
+        );
+        debug_assert!(ns.OtherUsers.is_empty());
+        debug_assert!(ns.Shared.len() == 2 && ns.Shared[0].Prefix == "#shared/");
+
+        let public = &ns.Shared[1];
+        debug_assert!(public.Delimiter.is_none());
+        debug_assert!(public.Extensions.len() == 1 && public.Extensions[0].0 == "X-PARAM");
+        debug_assert!(public.Extensions[0].1.as_ref() == ["FLAG1", "FLAG2"]);
+    })
+}
+
+#[test]
+fn TestId() {
+    smol::block_on(async {
+        let mut id = responses::Id::default();
+        handleAll(
+            &mut id,
+            b"* ID (\"Name\" \"Cyrus\" \"version\" \"1.5\" \"os\" NIL)\r\n",
+        )
+        .await;
+
+        let params = id.Params.unwrap();
+        debug_assert!(params.get("name").map(String::as_str) == Some("Cyrus"));
+        debug_assert!(params.get("version").map(String::as_str) == Some("1.5"));
+        debug_assert!(!params.contains_key("os"));
+
+        let mut id = responses::Id::default();
+        handleAll(&mut id, b"* ID NIL\r\n").await;
+        debug_assert!(id.Params.is_none());
+    })
+}
+
+#[test]
+fn TestQuota() {
+    smol::block_on(async {
+        let mut quota = responses::Quota::default();
+        handleAll(
+            &mut quota,
+            b"* QUOTAROOT INBOX \"\" \"#user/x\"\r\n\
+              * QUOTA \"\" (STORAGE 10 512 MESSAGE 20 20)\r\n\
+              * QUOTA \"#user/x\" (X-CUSTOM 1 2)\r\n",
+        )
+        .await;
+
+        debug_assert!(quota.Mailbox.as_deref() == Some("INBOX"));
+        debug_assert!(quota.Roots.as_ref() == ["", "#user/x"]);
+        debug_assert!(quota.Quotas.len() == 2);
+
+        let root = &quota.Quotas[0];
+        let storage = root.Get(&QuotaResource::Storage).unwrap();
+        debug_assert!(storage.Usage == 10 && storage.Limit == 512 && !storage.Exceeded());
+        debug_assert!(root
+            .Get(&QuotaResource::Message)
+            .map_or(false, |m| m.Exceeded()));
+        debug_assert!(
+            quota.Quotas[1].Resources[0].Resource == QuotaResource::Other("X-CUSTOM".into())
+        );
+
+        let resp = Reader::from(b"A003 NO [OVERQUOTA] Mailbox is full\r\n".bytes())
+            .ReadResp()
+            .await
+            .unwrap();
+        let err = resp.Check().unwrap_err();
+        debug_assert!(StatusError::From(&err).map_or(false, |e| e.Code == ErrorCode::OverQuota));
+    })
+}
+
+#[test]
+fn TestAcl() {
+    smol::block_on(async {
+        let mut acl = responses::Acl::default();
+        handleAll(
+            &mut acl,
+            b"* ACL INBOX Fred rwipslxetad \"Chris\" lrs\r\n\
+              * LISTRIGHTS ~/Mail/saved smith la r swicdkxte\r\n\
+              * MYRIGHTS INBOX rwiptsldaex\r\n",
+        )
+        .await;
+
+        debug_assert!(acl.Mailbox.as_deref() == Some("INBOX"));
+        debug_assert!(acl.Entries.len() == 2 && acl.Entries[1].0 == "Chris");
+        debug_assert!(acl.Entries[1].1 == Rights::Lookup | Rights::Read | Rights::Seen);
+
+        let list = acl.ListRights.unwrap();
+        debug_assert!(list.Identifier == "smith" && list.Required.to_string() == "la");
+        debug_assert!(list.Optional.len() == 2 && list.Optional[0] == Rights::Read);
+
+        let select = responses::select::MailboxStatus {
+            MyRights: acl.MyRights,
+            ..Default::default()
+        };
+        debug_assert!(select.Allows(Rights::Administer | Rights::Expunge));
+        debug_assert!(!select.Allows(Rights::CreateMailbox));
+
+        // Cyrus sends an `n` right
+        let mut acl = responses::Acl::default();
+        handleAll(&mut acl, b"* MYRIGHTS INBOX lrswipkxtecdan\r\n").await;
+        debug_assert!(acl
+            .MyRights
+            .map_or(false, |r| r.to_string() == "lrswipkxteacd"));
+    })
+}
+
+#[test]
+fn TestMetadata() {
+    smol::block_on(async {
+        let mut metadata = responses::Metadata::default();
+        handleAll(
+            &mut metadata,
+            b"* METADATA \"INBOX\" (/private/comment \"My comment\" /shared/comment NIL)\r\n\
+              * METADATA \"\" (/shared/motd {12}\r\nHello\r\nWorld /private/bin ~{2}\r\nab)\r\n\
+              * METADATA \"Sent\" /shared/comment /private/comment\r\n",
+        )
+        .await;
+
+        debug_assert!(
+            metadata.Entries[0]
+                == responses::MetadataEntry {
+                    Mailbox: "INBOX".into(),
+                    Entry: "/private/comment".into(),
+                    Value: Some("My comment".into()),
+                }
+        );
+        debug_assert!(metadata.Entries[1].Value.is_none());
+        debug_assert!(
+            metadata.Entries[2].Mailbox == ""
+                && metadata.Entries[2].Value.as_deref() == Some("Hello\r\nWorld")
+        );
+        debug_assert!(metadata.Entries[3].Value.as_deref() == Some("ab"));
+        debug_assert!(metadata.Changed.len() == 2 && metadata.Changed[0].0 == "Sent");
+
+        let check = |input: &'static [u8]| async move {
+            let resp = Reader::from(input.bytes()).ReadResp().await.unwrap();
+            match resp {
+                Resp::StatResp(status) => status,
+                _ => unreachable!(),
+            }
+        };
+
+        let status = check(b"a001 OK [METADATA LONGENTRIES 2199] done\r\n").await;
+        debug_assert!(status.LongEntries() == Some(2199));
+
+        let status = check(b"a001 NO [METADATA MAXSIZE 1024] too big\r\n").await;
+        let err = status.Check().unwrap_err();
+        debug_assert!(
+            StatusError::From(&err).map_or(false, |e| e.Code == ErrorCode::MetadataMaxSize(1024))
+        );
+
+        let status = check(b"a001 NO [METADATA TOOMANY] too many\r\n").await;
+        let err = status.Check().unwrap_err();
+        debug_assert!(
+            StatusError::From(&err).map_or(false, |e| e.Code == ErrorCode::MetadataTooMany)
+        );
+    })
+}
+
+#[test]
+fn TestSort() {
+    smol::block_on(async {
+        let mut sort = responses::Sort::default();
+        handleAll(&mut sort, b"* SORT 2 84 882\r\n").await;
+        debug_assert!(sort.Ids.as_ref() == [2, 84, 882]);
+
+        let mut sort = responses::Sort::default();
+        handleAll(&mut sort, b"* SORT\r\n").await;
+        debug_assert!(sort.Ids.is_empty());
+    })
+}
+
+#[test]
+fn TestThread() {
+    smol::block_on(async {
+        let node = |id, children| ThreadNode {
+            Id: id,
+            Children: children,
+        };
+
+        let mut thread = responses::Thread::default();
+        handleAll(
+            &mut thread,
+            b"* THREAD (2)(3 6 (4 23)(44 7 96))((3)(5))\r\n",
+        )
+        .await;
+        debug_assert!(
+            thread.Threads
+                == [
+                    node(Some(2), vec![]),
+                    node(
+                        Some(3),
+                        vec![node(
+                            Some(6),
+                            vec![
+                                node(Some(4), vec![node(Some(23), vec![])]),
+                                node(Some(44), vec![node(Some(7), vec![node(Some(96), vec![])])]),
+                            ]
+                        )]
+                    ),
+                    node(None, vec![node(Some(3), vec![]), node(Some(5), vec![])]),
+                ]
+        );
+        debug_assert!(thread.Threads[1].Ids() == [3, 6, 4, 23, 44, 7, 96]);
+
+        let mut thread = responses::Thread::default();
+        handleAll(&mut thread, b"* THREAD\r\n").await;
+        debug_assert!(thread.Threads.is_empty());
+    })
+}
+
+#[test]
+fn TestParseFetch_Envelope() {
+    smol::block_on(async {
+        let resp = Reader::from(
+            &b"* 12 FETCH (UID 20 ENVELOPE (\"Wed, 17 Jul 1996 02:23:25 -0700 (PDT)\" \"IMAP4rev1 WG mtg summary\" \
+               ((\"Terry Gray\" NIL \"gray\" \"cac.washington.edu\")) NIL NIL \
+               ((NIL NIL \"imap\" \"cac.washington.edu\")) NIL NIL \"<B27397-0100000@cac.washington.edu>\" \
+               \"<B27397-0200000@cac.washington.edu>\") \
+               BODY[HEADER.FIELDS (REFERENCES)] {69}\r\n\
+               References: <a@example.com>\r\n <B27397-0100000@cac.washington.edu>\r\n\r\n)\r\n"[..],
+        )
+        .ReadResp()
+        .await
+        .unwrap();
+
+        let msg = fetch::ParseFetch(&resp).await.unwrap().unwrap();
+        let envelope = msg.Envelope.as_ref().unwrap();
+        debug_assert!(envelope.Subject.as_deref() == Some("IMAP4rev1 WG mtg summary"));
+        debug_assert!(
+            envelope.From
+                == [Address {
+                    Name: Some("Terry Gray".into()),
+                    Route: None,
+                    Mailbox: Some("gray".into()),
+                    Host: Some("cac.washington.edu".into()),
+                }]
+        );
+        debug_assert!(envelope.Sender.is_empty());
+        debug_assert!(envelope.To[0].Email().as_deref() == Some("imap@cac.washington.edu"));
+        debug_assert!(envelope.InReplyTo.as_deref() == Some("<B27397-0100000@cac.washington.edu>"));
+        debug_assert!(
+            msg.HeaderField("references").as_deref()
+                == Some("<a@example.com> <B27397-0100000@cac.washington.edu>")
+        );
+        debug_assert!(msg.HeaderField("Subject").is_none());
+    })
+}
+
+#[test]
+fn TestSelect_CondStore() {
+    smol::block_on(async {
+        let mut select = responses::Select::default();
+        handleAll(
+            &mut select,
+            b"* 172 EXISTS\r\n\
+              * OK [UIDVALIDITY 3857529045] UIDs valid\r\n\
+              * OK [HIGHESTMODSEQ 715194045007] Highest\r\n\
+              * OK [MAILBOXID (F2212ea87-6097-4256-9d51-71338625)] Ok\r\n\
+              * LIST () \"/\" INBOX\r\n\
+              a001 OK [READ-ONLY] EXAMINE completed\r\n",
+        )
+        .await;
+        debug_assert!(select.Mailbox.Messages == 172);
+        debug_assert!(select.Mailbox.HighestModSeq == Some(715194045007));
+        debug_assert!(select.Mailbox.ReadOnly);
+
+        handleAll(&mut select, b"* OK [NOMODSEQ] Sorry\r\n").await;
+        debug_assert!(select.Mailbox.HighestModSeq.is_none());
+    })
+}
+
+#[test]
+fn TestCondStore_Responses() {
+    smol::block_on(async {
+        let resp =
+            Reader::from(b"* 4 FETCH (UID 8 MODSEQ (12121231000) FLAGS (\\Seen))\r\n".bytes())
+                .ReadResp()
+                .await
+                .unwrap();
+        let msg = fetch::ParseFetch(&resp).await.unwrap().unwrap();
+        debug_assert!(msg.ModSeq == Some(12121231000));
+
+        let mut sort = responses::Sort::default();
+        handleAll(&mut sort, b"* SORT 2 84 882 (MODSEQ 917162500)\r\n").await;
+        debug_assert!(sort.Ids.as_ref() == [2, 84, 882]);
+        debug_assert!(sort.ModSeq == Some(917162500));
+
+        let mut search = responses::Search::default();
+        handleAll(
+            &mut search,
+            b"* SEARCH 2 5 6 7 11 12 18 19 20 23 (MODSEQ 917162500)\r\n",
+        )
+        .await;
+        debug_assert!(search.Ids.as_ref() == [2, 5, 6, 7, 11, 12, 18, 19, 20, 23]);
+        debug_assert!(search.ModSeq == Some(917162500));
+
+        let mut search = responses::Search::default();
+        handleAll(&mut search, b"* SEARCH\r\n").await;
+        debug_assert!(search.Ids.is_empty() && search.ModSeq.is_none());
+
+        let resp = Reader::from(b"a001 OK [MODIFIED 7,9] Conditional STORE failed\r\n".bytes())
+            .ReadResp()
+            .await
+            .unwrap();
+        match &resp {
+            Resp::StatResp(status) => {
+                debug_assert!(status.Modified().map(|s| s.to_string()).as_deref() == Some("7,9"))
+            }
+            _ => unreachable!(),
+        }
+        debug_assert!(resp.Check().is_ok());
+
+        let resp = Reader::from(b"a001 NO [MODIFIED 2] Conditional STORE failed\r\n".bytes())
+            .ReadResp()
+            .await
+            .unwrap();
+        let err = resp.Check().unwrap_err();
+        debug_assert!(
+            StatusError::From(&err).unwrap().Code == ErrorCode::Modified("2".parse().unwrap())
+        );
+    })
+}
+
+#[test]
+fn TestResync() {
+    smol::block_on(async {
+        let mut resync = responses::Resync::default();
+        handleAll(
+            &mut resync,
+            b"* OK [CLOSED]\r\n\
+              * 100 EXISTS\r\n\
+              * OK [UIDVALIDITY 67890007] UIDVALIDITY\r\n\
+              * OK [HIGHESTMODSEQ 90060115205545359] Highest mailbox mod-sequence\r\n\
+              * VANISHED (EARLIER) 41,43:116,118,120:211,214:540\r\n\
+              * 49 FETCH (UID 117 FLAGS (\\Seen \\Answered) MODSEQ (90060115194045001))\r\n\
+              * 50 FETCH (UID 119 FLAGS (\\Draft $MDNSent) MODSEQ (90060115194045308))\r\n\
+              A02 OK [READ-WRITE] Sorry, UIDVALIDITY mismatch\r\n",
+        )
+        .await;
+
+        debug_assert!(resync.Mailbox.Messages == 100);
+        debug_assert!(resync.Mailbox.UidValidity == 67890007);
+        debug_assert!(resync.Mailbox.HighestModSeq == Some(90060115205545359));
+        debug_assert!(resync.Vanished.to_string() == "41,43:116,118,120:211,214:540");
+        debug_assert!(
+            resync
+                .Changed
+                .iter()
+                .map(|m| (m.Uid, m.ModSeq))
+                .collect::<Vec<_>>()
+                == [
+                    (Some(117), Some(90060115194045001)),
+                    (Some(119), Some(90060115194045308))
+                ]
+        );
+
+        let resp = Reader::from(b"* VANISHED 405,707\r\n".bytes())
+            .ReadResp()
+            .await
+            .unwrap();
+        let (uids, earlier) = resync::ParseVanished(&resp).await.unwrap().unwrap();
+        debug_assert!(uids.to_string() == "405,707" && !earlier);
+
+        let resp = Reader::from(b"* 3 EXPUNGE\r\n".bytes())
+            .ReadResp()
+            .await
+            .unwrap();
+        debug_assert!(resync::ParseVanished(&resp).await.unwrap().is_none());
+    })
+}
@@ -0,0 +1,97 @@
+use std::borrow::Cow;
+
+use super::Handler;
+use crate::{
+    read::TY,
+    response::{self, Resp},
+    utf7, Rights,
+};
+use async_trait::async_trait;
+use futures_lite::io;
+
+/// The `* ACL`, `* LISTRIGHTS` and `* MYRIGHTS` responses (RFC 4314).
+#[derive(Debug, Default, Clone)]
+pub struct Acl<'s> {
+    pub Mailbox: Option<Cow<'s, str>>,
+    /// The identifiers and their rights, from `* ACL`.
+    pub Entries: Cow<'s, [(Cow<'s, str>, Rights)]>,
+    /// From `* LISTRIGHTS`.
+    pub ListRights: Option<ListRightsInfo<'s>>,
+    /// From `* MYRIGHTS`.
+    pub MyRights: Option<Rights>,
+    /// Set once UTF8=ACCEPT is enabled, the mailbox name is then UTF-8.
+    pub Utf8: bool,
+}
+
+/// The rights that can be granted to an identifier: `Required` are always
+/// granted, and each group of `Optional` can only be granted as a whole.
+#[derive(Debug, Default, Clone, PartialEq, Eq)]
+pub struct ListRightsInfo<'l> {
+    pub Identifier: Cow<'l, str>,
+    pub Required: Rights,
+    pub Optional: Cow<'l, [Rights]>,
+}
+
+fn parseRights(field: &TY<'_>) -> io::Result<Rights> {
+    field
+        .AsStr()
+        .ok_or_else(|| io::Error::new(io::ErrorKind::Other, "rights are not a string"))?
+        .parse()
+}
+
+#[async_trait]
+impl<'s> Handler<'s> for Acl<'s> {
+    async fn Handle(&mut self, resp: &mut Resp<'s>) -> io::Result<()> {
+        let resp = match resp {
+            Resp::DataResp(resp) => resp,
+            _ => return Ok(()),
+        };
+
+        let (name, fields) = response::ParseNamedResp(resp).await?;
+        if !["ACL", "LISTRIGHTS", "MYRIGHTS"].contains(&name.as_ref()) {
+            return Ok(());
+        }
+
+        let (mailbox, rest) = match fields.split_first() {
+            Some((TY::Str(mailbox), rest)) => (mailbox, rest),
+            _ => {
+                return Err(io::Error::new(
+                    io::ErrorKind::Other,
+                    format!("invalid {} response", name),
+                ))
+            }
+        };
+        self.Mailbox = Some(utf7::DecodeName(mailbox, self.Utf8));
+
+        match (name.as_ref(), rest) {
+            ("ACL", entries) => {
+                for pair in entries.chunks(2) {
+                    if let [TY::Str(identifier), rights] = pair {
+                        self.Entries
+                            .to_mut()
+                            .push((identifier.clone(), parseRights(rights)?));
+                    }
+                }
+            }
+            ("LISTRIGHTS", [TY::Str(identifier), required, optional @ ..]) => {
+                self.ListRights = Some(ListRightsInfo {
+                    Identifier: identifier.clone(),
+                    Required: parseRights(required)?,
+                    Optional: optional
+                        .iter()
+                        .map(parseRights)
+                        .collect::<io::Result<_>>()?,
+                });
+            }
+            ("MYRIGHTS", [rights]) => self.MyRights = Some(parseRights(rights)?),
+            _ => {
+                return Err(io::Error::new(
+                    io::ErrorKind::Other,
+                    format!("invalid {} response", name),
+                ))
+            }
+        }
+
+        Ok(())
+    }
+}
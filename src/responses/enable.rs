@@ -0,0 +1,37 @@
+use std::borrow::Cow;
+
+use super::Handler;
+use crate::{
+    read::TY,
+    response::{self, Resp},
+};
+use async_trait::async_trait;
+use futures_lite::io;
+
+/// The extensions listed by `* ENABLED` responses.
+#[derive(Debug, Default, Clone)]
+pub struct Enable<'s> {
+    pub Enabled: Cow<'s, [Cow<'s, str>]>,
+}
+
+#[async_trait]
+impl<'s> Handler<'s> for Enable<'s> {
+    async fn Handle(&mut self, resp: &mut Resp<'s>) -> io::Result<()> {
+        let resp = match resp {
+            Resp::DataResp(resp) => resp,
+            _ => return Ok(()),
+        };
+
+        let (name, fields) = response::ParseNamedResp(resp).await?;
+        if name == "ENABLED" {
+            self.Enabled.to_mut().extend(
+                fields
+                    .iter()
+                    .filter_map(TY::AsStr)
+                    .map(|c| Cow::Owned(c.to_string())),
+            );
+        }
+
+        Ok(())
+    }
+}
@@ -0,0 +1,93 @@
+use crate::read::TY;
+use futures_lite::io;
+
+/// The ENVELOPE fetch item: the main header fields of a message, as parsed
+/// by the server. Header values are kept as sent, RFC 2047 encoded words
+/// included.
+#[derive(Debug, Default, Clone, PartialEq, Eq)]
+pub struct Envelope {
+    pub Date: Option<String>,
+    pub Subject: Option<String>,
+    pub From: Vec<Address>,
+    pub Sender: Vec<Address>,
+    pub ReplyTo: Vec<Address>,
+    pub To: Vec<Address>,
+    pub Cc: Vec<Address>,
+    pub Bcc: Vec<Address>,
+    pub InReplyTo: Option<String>,
+    pub MessageId: Option<String>,
+}
+
+#[derive(Debug, Default, Clone, PartialEq, Eq)]
+pub struct Address {
+    /// The display name.
+    pub Name: Option<String>,
+    /// The obsolete source route.
+    pub Route: Option<String>,
+    pub Mailbox: Option<String>,
+    pub Host: Option<String>,
+}
+
+impl Address {
+    /// `mailbox@host`, `None` for the markers around a group.
+    pub fn Email(&self) -> Option<String> {
+        match (&self.Mailbox, &self.Host) {
+            (Some(mailbox), Some(host)) => Some(format!("{}@{}", mailbox, host)),
+            _ => None,
+        }
+    }
+}
+
+fn invalid() -> io::Error {
+    io::Error::new(io::ErrorKind::Other, "invalid ENVELOPE")
+}
+
+fn nstring(item: &TY<'_>) -> io::Result<Option<String>> {
+    match item {
+        TY::Str(s) => Ok(Some(s.to_string())),
+        TY::Nil => Ok(None),
+        TY::List(_) => Err(invalid()),
+    }
+}
+
+fn addresses(item: &TY<'_>) -> io::Result<Vec<Address>> {
+    let list = match item {
+        TY::Nil => return Ok(Vec::new()),
+        TY::List(list) => list,
+        TY::Str(_) => return Err(invalid()),
+    };
+
+    list.iter()
+        .map(|addr| match addr.AsList() {
+            Some([name, route, mailbox, host]) => Ok(Address {
+                Name: nstring(name)?,
+                Route: nstring(route)?,
+                Mailbox: nstring(mailbox)?,
+                Host: nstring(host)?,
+            }),
+            _ => Err(invalid()),
+        })
+        .collect()
+}
+
+impl Envelope {
+    pub fn Parse(items: &[TY<'_>]) -> io::Result<Envelope> {
+        match items {
+            [date, subject, from, sender, replyTo, to, cc, bcc, inReplyTo, messageId] => {
+                Ok(Envelope {
+                    Date: nstring(date)?,
+                    Subject: nstring(subject)?,
+                    From: addresses(from)?,
+                    Sender: addresses(sender)?,
+                    ReplyTo: addresses(replyTo)?,
+                    To: addresses(to)?,
+                    Cc: addresses(cc)?,
+                    Bcc: addresses(bcc)?,
+                    InReplyTo: nstring(inReplyTo)?,
+                    MessageId: nstring(messageId)?,
+                })
+            }
+            _ => Err(invalid()),
+        }
+    }
+}
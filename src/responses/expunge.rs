@@ -0,0 +1,43 @@
+use std::borrow::Cow;
+
+use super::Handler;
+use crate::{
+    read::TY,
+    response::{self, CopyUid, Resp},
+};
+use async_trait::async_trait;
+use futures_lite::io;
+
+/// Collects the `* n EXPUNGE` responses sent for EXPUNGE and MOVE.
+#[derive(Debug, Default, Clone)]
+pub struct Expunge<'s> {
+    /// Sequence numbers in the order the server sent them. Each number is
+    /// relative to the mailbox after the previous ones were removed.
+    pub SeqNums: Cow<'s, [u32]>,
+    /// The `[COPYUID]` code a MOVE sends in an untagged OK response.
+    pub CopyUid: Option<CopyUid>,
+}
+
+#[async_trait]
+impl<'s> Handler<'s> for Expunge<'s> {
+    async fn Handle(&mut self, resp: &mut Resp<'s>) -> io::Result<()> {
+        match resp {
+            Resp::StatResp(status) => {
+                if let Some(copyUid) = status.CopyUid() {
+                    self.CopyUid = Some(copyUid);
+                }
+            }
+            Resp::DataResp(resp) => {
+                let (name, fields) = response::ParseNamedResp(resp).await?;
+                if name == "EXPUNGE" {
+                    if let Some(seqNum) = fields.get(0).and_then(TY::Parse) {
+                        self.SeqNums.to_mut().push(seqNum);
+                    }
+                }
+            }
+            _ => {}
+        }
+
+        Ok(())
+    }
+}
@@ -0,0 +1,192 @@
+use std::borrow::Cow;
+
+use super::{Envelope, Handler};
+use crate::{
+    date,
+    flag::Flag,
+    read::TY,
+    response::{self, Resp},
+};
+use async_trait::async_trait;
+use futures_lite::io;
+
+#[derive(Debug, Default, Clone)]
+pub struct Fetch<'s> {
+    pub Messages: Cow<'s, [Message<'s>]>,
+}
+
+/// The data items of a `* n FETCH (...)` response. Items the server did not
+/// send are left as `None`.
+#[derive(Debug, Default, Clone, PartialEq, Eq)]
+pub struct Message<'m> {
+    pub SeqNum: u32,
+    pub Uid: Option<u32>,
+    pub Flags: Option<Cow<'m, [Flag]>>,
+    pub Envelope: Option<Envelope>,
+    /// The header fields sent for `BODY[HEADER]` or
+    /// `BODY[HEADER.FIELDS (...)]`.
+    pub Header: Option<Cow<'m, str>>,
+    pub InternalDate: Option<Cow<'m, str>>,
+    /// RFC822.SIZE, in bytes.
+    pub Size: Option<u32>,
+    /// The mod-sequence of the message (RFC 7162).
+    pub ModSeq: Option<u64>,
+}
+
+impl<'m> Message<'m> {
+    pub fn Parse(seqNum: u32, items: &[TY<'m>]) -> io::Result<Message<'m>> {
+        let mut msg = Message {
+            SeqNum: seqNum,
+            ..Default::default()
+        };
+
+        let mut items = items.iter();
+        while let Some(key) = items.next() {
+            let key = key.AsStr().ok_or_else(|| {
+                io::Error::new(io::ErrorKind::Other, "fetch item name is not an atom")
+            })?;
+            let value = items.next().ok_or_else(|| {
+                io::Error::new(
+                    io::ErrorKind::Other,
+                    format!("fetch item {} has no value", key),
+                )
+            })?;
+
+            let key = key.to_uppercase();
+            match key.as_str() {
+                "UID" => msg.Uid = value.Parse(),
+                "FLAGS" => msg.Flags = value.AsList().map(Flag::ParseList),
+                "INTERNALDATE" => msg.InternalDate = value.AsStr().map(|d| d.to_string().into()),
+                "RFC822.SIZE" => msg.Size = value.Parse(),
+                "MODSEQ" => msg.ModSeq = value.AsList().and_then(|l| l.first()?.Parse()),
+                "ENVELOPE" => {
+                    msg.Envelope = Some(Envelope::Parse(value.AsList().ok_or_else(|| {
+                        io::Error::new(io::ErrorKind::Other, "invalid ENVELOPE")
+                    })?)?)
+                }
+                _ if key.starts_with("BODY[HEADER") || key == "RFC822.HEADER" => {
+                    msg.Header = match value {
+                        TY::Str(s) => Some(s.clone()),
+                        _ => Some("".into()),
+                    }
+                }
+                _ => {}
+            }
+        }
+
+        Ok(msg)
+    }
+
+    /// Merges the items of a newer FETCH response for the same message.
+    pub fn Update(&mut self, other: Message<'_>) {
+        self.SeqNum = other.SeqNum;
+
+        if other.Uid.is_some() {
+            self.Uid = other.Uid;
+        }
+
+        if let Some(flags) = other.Flags {
+            self.Flags = Some(Cow::Owned(flags.into_owned()));
+        }
+
+        if other.Envelope.is_some() {
+            self.Envelope = other.Envelope;
+        }
+
+        if let Some(header) = other.Header {
+            self.Header = Some(Cow::Owned(header.into_owned()));
+        }
+
+        if let Some(date) = other.InternalDate {
+            self.InternalDate = Some(Cow::Owned(date.into_owned()));
+        }
+
+        if other.Size.is_some() {
+            self.Size = other.Size;
+        }
+
+        if other.ModSeq.is_some() {
+            self.ModSeq = other.ModSeq;
+        }
+    }
+
+    /// When the message arrived in the mailbox, from `InternalDate`, in
+    /// seconds since the Unix epoch.
+    pub fn ArrivalDate(&self) -> Option<i64> {
+        self.InternalDate.as_deref().and_then(date::Parse)
+    }
+
+    /// When the message was sent, from the envelope's Date, or when it
+    /// arrived if the Date is missing or invalid, as RFC 5256 orders them.
+    pub fn SentDate(&self) -> Option<i64> {
+        self.Envelope
+            .as_ref()
+            .and_then(|e| e.Date.as_deref())
+            .and_then(date::Parse)
+            .or_else(|| self.ArrivalDate())
+    }
+
+    /// The unfolded value of the first `name` field of `Header`.
+    pub fn HeaderField(&self, name: &str) -> Option<String> {
+        let header = self.Header.as_ref()?;
+
+        let mut value: Option<String> = None;
+        for line in header.lines() {
+            if line.starts_with([' ', '\t']) {
+                if let Some(value) = value.as_mut() {
+                    value.push(' ');
+                    value.push_str(line.trim());
+                }
+                continue;
+            }
+
+            if value.is_some() {
+                break;
+            }
+
+            if let Some((field, rest)) = line.split_once(':') {
+                if field.trim().eq_ignore_ascii_case(name) {
+                    value = Some(rest.trim().to_string());
+                }
+            }
+        }
+
+        value
+    }
+}
+
+/// Parses an untagged `* n FETCH (...)` response, returns `None` for any
+/// other response.
+pub async fn ParseFetch<'a>(resp: &Resp<'a>) -> io::Result<Option<Message<'a>>> {
+    let resp = match resp {
+        Resp::DataResp(resp) => resp,
+        _ => return Ok(None),
+    };
+
+    let (name, fields) = response::ParseNamedResp(resp).await?;
+    if name != "FETCH" {
+        return Ok(None);
+    }
+
+    match (
+        fields.get(0).and_then(TY::Parse),
+        fields.get(1).and_then(TY::AsList),
+    ) {
+        (Some(seqNum), Some(items)) => Ok(Some(Message::Parse(seqNum, items)?)),
+        _ => Err(io::Error::new(
+            io::ErrorKind::Other,
+            "invalid FETCH response",
+        )),
+    }
+}
+
+#[async_trait]
+impl<'s> Handler<'s> for Fetch<'s> {
+    async fn Handle(&mut self, resp: &mut Resp<'s>) -> io::Result<()> {
+        if let Some(msg) = ParseFetch(resp).await? {
+            self.Messages.to_mut().push(msg);
+        }
+
+        Ok(())
+    }
+}
@@ -0,0 +1,53 @@
+use std::collections::BTreeMap;
+
+use super::Handler;
+use crate::{
+    read::TY,
+    response::{self, Resp},
+};
+use async_trait::async_trait;
+use futures_lite::io;
+
+/// The `* ID (...)` response. Field names are lower-cased, as they are
+/// case-insensitive, and NIL values are left out.
+#[derive(Debug, Default, Clone)]
+pub struct Id {
+    /// `None` when the server answered `ID NIL`.
+    pub Params: Option<BTreeMap<String, String>>,
+}
+
+impl Id {
+    pub fn Parse(fields: &[TY<'_>]) -> io::Result<Option<BTreeMap<String, String>>> {
+        let list = match fields.get(0) {
+            Some(TY::Nil) => return Ok(None),
+            Some(TY::List(list)) => list,
+            _ => return Err(io::Error::new(io::ErrorKind::Other, "invalid ID response")),
+        };
+
+        let mut params = BTreeMap::new();
+        for pair in list.chunks(2) {
+            if let [TY::Str(key), TY::Str(value)] = pair {
+                params.insert(key.to_lowercase(), value.to_string());
+            }
+        }
+
+        Ok(Some(params))
+    }
+}
+
+#[async_trait]
+impl<'s> Handler<'s> for Id {
+    async fn Handle(&mut self, resp: &mut Resp<'s>) -> io::Result<()> {
+        let resp = match resp {
+            Resp::DataResp(resp) => resp,
+            _ => return Ok(()),
+        };
+
+        let (name, fields) = response::ParseNamedResp(resp).await?;
+        if name == "ID" {
+            self.Params = Id::Parse(&fields)?;
+        }
+
+        Ok(())
+    }
+}
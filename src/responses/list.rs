@@ -1,9 +1,13 @@
 use std::borrow::Cow;
 
-use super::Handler;
+use super::{
+    status::{self, StatusInfo},
+    Handler,
+};
 use crate::{
     read::TY,
     response::{self, Resp},
+    utf7,
 };
 use async_trait::async_trait;
 use futures_lite::io;
@@ -11,17 +15,138 @@ use futures_lite::io;
 #[derive(Debug, Default, Clone)]
 pub struct List<'s> {
     pub Mailboxes: Cow<'s, [MailboxInfo<'s>]>,
+    /// Mailbox names are UTF-8 rather than modified UTF-7, set once
+    /// UTF8=ACCEPT is enabled.
+    pub Utf8: bool,
+}
+
+/// A mailbox name attribute, from RFC 3501, RFC 5258 and RFC 6154.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub enum MailboxAttribute {
+    Noinferiors,
+    Noselect,
+    NonExistent,
+    HasChildren,
+    HasNoChildren,
+    Marked,
+    Unmarked,
+    Subscribed,
+    Remote,
+    // Special-use roles (RFC 6154)
+    All,
+    Archive,
+    Drafts,
+    Flagged,
+    Junk,
+    Sent,
+    Trash,
+    Other(String),
+}
+
+impl MailboxAttribute {
+    /// Reports whether the attribute is a RFC 6154 special-use role.
+    pub fn IsSpecialUse(&self) -> bool {
+        matches!(
+            self,
+            MailboxAttribute::All
+                | MailboxAttribute::Archive
+                | MailboxAttribute::Drafts
+                | MailboxAttribute::Flagged
+                | MailboxAttribute::Junk
+                | MailboxAttribute::Sent
+                | MailboxAttribute::Trash
+        )
+    }
+
+    /// Mailbox names commonly used for a special-use role by servers that
+    /// don't advertise SPECIAL-USE.
+    pub fn CommonNames(&self) -> &'static [&'static str] {
+        match self {
+            MailboxAttribute::All => &["All Mail", "[Gmail]/All Mail"],
+            MailboxAttribute::Archive => &["Archive", "Archives"],
+            MailboxAttribute::Drafts => &["Drafts", "Draft", "[Gmail]/Drafts"],
+            MailboxAttribute::Flagged => &["Flagged", "Starred", "[Gmail]/Starred"],
+            MailboxAttribute::Junk => &["Junk", "Spam", "Junk E-mail", "[Gmail]/Spam"],
+            MailboxAttribute::Sent => &["Sent", "Sent Items", "Sent Messages", "[Gmail]/Sent Mail"],
+            MailboxAttribute::Trash => &[
+                "Trash",
+                "Deleted Items",
+                "Deleted Messages",
+                "[Gmail]/Trash",
+            ],
+            _ => &[],
+        }
+    }
+}
+
+impl std::fmt::Display for MailboxAttribute {
+    fn fmt(&self, fmt: &mut std::fmt::Formatter) -> std::fmt::Result {
+        fmt.write_str(match self {
+            MailboxAttribute::Noinferiors => "\\Noinferiors",
+            MailboxAttribute::Noselect => "\\Noselect",
+            MailboxAttribute::NonExistent => "\\NonExistent",
+            MailboxAttribute::HasChildren => "\\HasChildren",
+            MailboxAttribute::HasNoChildren => "\\HasNoChildren",
+            MailboxAttribute::Marked => "\\Marked",
+            MailboxAttribute::Unmarked => "\\Unmarked",
+            MailboxAttribute::Subscribed => "\\Subscribed",
+            MailboxAttribute::Remote => "\\Remote",
+            MailboxAttribute::All => "\\All",
+            MailboxAttribute::Archive => "\\Archive",
+            MailboxAttribute::Drafts => "\\Drafts",
+            MailboxAttribute::Flagged => "\\Flagged",
+            MailboxAttribute::Junk => "\\Junk",
+            MailboxAttribute::Sent => "\\Sent",
+            MailboxAttribute::Trash => "\\Trash",
+            MailboxAttribute::Other(a) => a,
+        })
+    }
+}
+
+impl std::str::FromStr for MailboxAttribute {
+    type Err = io::Error;
+
+    fn from_str(s: &str) -> io::Result<MailboxAttribute> {
+        // Attributes are case-insensitive
+        Ok(match s.to_ascii_lowercase().as_str() {
+            "\\noinferiors" => MailboxAttribute::Noinferiors,
+            "\\noselect" => MailboxAttribute::Noselect,
+            "\\nonexistent" => MailboxAttribute::NonExistent,
+            "\\haschildren" => MailboxAttribute::HasChildren,
+            "\\hasnochildren" => MailboxAttribute::HasNoChildren,
+            "\\marked" => MailboxAttribute::Marked,
+            "\\unmarked" => MailboxAttribute::Unmarked,
+            "\\subscribed" => MailboxAttribute::Subscribed,
+            "\\remote" => MailboxAttribute::Remote,
+            "\\all" => MailboxAttribute::All,
+            "\\archive" => MailboxAttribute::Archive,
+            "\\drafts" => MailboxAttribute::Drafts,
+            "\\flagged" => MailboxAttribute::Flagged,
+            "\\junk" => MailboxAttribute::Junk,
+            "\\sent" => MailboxAttribute::Sent,
+            "\\trash" => MailboxAttribute::Trash,
+            _ => MailboxAttribute::Other(s.to_string()),
+        })
+    }
 }
 
 #[derive(Debug, Default, Clone)]
 pub struct MailboxInfo<'m> {
-    Attributes: Cow<'m, [Cow<'m, str>]>,
-    Delimiter: Cow<'m, str>,
-    Name: Cow<'m, str>,
+    pub Attributes: Cow<'m, [MailboxAttribute]>,
+    pub Delimiter: Cow<'m, str>,
+    pub Name: Cow<'m, str>,
+    /// CHILDINFO extended data (RFC 5258): the selection options matched by
+    /// inferiors of this mailbox, e.g. `SUBSCRIBED`.
+    pub ChildInfo: Cow<'m, [Cow<'m, str>]>,
+    /// OLDNAME extended data (RFC 5465): the name before a rename.
+    pub OldName: Option<Cow<'m, str>>,
+    /// The STATUS sent along with the mailbox for the STATUS return option
+    /// (RFC 5819).
+    pub Status: Option<StatusInfo<'m>>,
 }
 
 impl<'m> MailboxInfo<'m> {
-    fn Parse(fields: Cow<'_, [TY<'m>]>) -> io::Result<MailboxInfo<'m>> {
+    fn Parse(fields: Cow<'_, [TY<'m>]>, utf8: bool) -> io::Result<MailboxInfo<'m>> {
         let mut mbox = MailboxInfo::default();
 
         if fields.len() < 3 {
@@ -38,30 +163,259 @@ impl<'m> MailboxInfo<'m> {
                 None
             }
         }) {
-            mbox.Attributes = list.to_owned();
+            mbox.Attributes = list
+                .iter()
+                .filter_map(TY::AsStr)
+                .filter_map(|a| a.parse().ok())
+                .collect();
         }
 
         mbox.Delimiter = match fields.get(1) {
             Some(TY::Str(name)) => name.to_owned(),
+            Some(TY::Nil) => "".into(),
             _ => " ".into(),
         };
 
         mbox.Name = match fields.get(2) {
-            Some(TY::Str(name)) => name.to_owned(),
+            Some(TY::Str(name)) => utf7::DecodeName(name, utf8),
             _ => " ".into(),
         };
 
+        if let Some(TY::List(extended)) = fields.get(3) {
+            for pair in extended.chunks(2) {
+                let (tag, value) = match pair {
+                    [TY::Str(tag), TY::List(value)] => (tag, value),
+                    _ => continue,
+                };
+
+                match tag.to_uppercase().as_str() {
+                    "CHILDINFO" => {
+                        mbox.ChildInfo = value
+                            .iter()
+                            .filter_map(|v| v.AsStr().map(|v| Cow::Owned(v.to_string())))
+                            .collect();
+                    }
+                    "OLDNAME" => {
+                        mbox.OldName = match value.get(0) {
+                            Some(TY::Str(name)) => {
+                                Some(Cow::Owned(utf7::DecodeName(name, utf8).into_owned()))
+                            }
+                            _ => None,
+                        };
+                    }
+                    _ => {}
+                }
+            }
+        }
+
         Ok(mbox)
     }
 }
 
+impl<'s> List<'s> {
+    pub fn Get(&self, name: &str) -> Option<&MailboxInfo<'s>> {
+        self.Mailboxes.iter().find(|m| sameName(&m.Name, name))
+    }
+
+    /// Arranges the mailboxes into their hierarchy.
+    pub fn Tree<'t>(&self) -> super::MailboxTree<'t> {
+        super::MailboxTree::Build(self)
+    }
+
+    /// Finds the mailbox with a special-use role. Mailboxes marked with the
+    /// role are preferred, then the first match among the role's common
+    /// names, compared case-insensitively.
+    pub fn FindSpecialUse(&self, role: &MailboxAttribute) -> Option<&MailboxInfo<'s>> {
+        if let Some(mbox) = self.Mailboxes.iter().find(|m| m.Has(role)) {
+            return Some(mbox);
+        }
+
+        role.CommonNames().iter().find_map(|name| {
+            self.Mailboxes
+                .iter()
+                .find(|m| m.Name.eq_ignore_ascii_case(name) && !m.Has(&MailboxAttribute::Noselect))
+        })
+    }
+
+    /// The hierarchy delimiter used by the listed mailboxes, if any.
+    pub fn Delimiter(&self) -> Option<&str> {
+        self.Mailboxes
+            .iter()
+            .map(|m| m.Delimiter.as_ref())
+            .find(|d| !d.trim().is_empty())
+    }
+
+    /// Adds the mailboxes of `other`, replacing the ones with the same name.
+    pub fn Merge(&mut self, other: &List<'_>) {
+        for mbox in other.Mailboxes.iter() {
+            self.mergeOne(mbox);
+        }
+    }
+
+    /// Replaces the mailboxes under `reference` with the result of a LIST
+    /// of `reference*`, so the ones deleted by another client are dropped.
+    /// What is known about their subscriptions is kept.
+    pub fn Replace(&mut self, reference: &str, other: &List<'_>) {
+        self.Mailboxes
+            .to_mut()
+            .retain(|m| !m.Name.starts_with(reference) || other.Get(&m.Name).is_some());
+        self.Merge(other);
+    }
+
+    /// Adds the result of an LSUB, marking the known mailboxes as subscribed
+    /// without losing what LIST said about them.
+    pub fn MergeSubscribed(&mut self, other: &List<'_>) {
+        for mbox in other.Mailboxes.iter() {
+            if self.Get(&mbox.Name).is_some() {
+                self.SetSubscribed(&mbox.Name, true);
+            } else {
+                self.mergeOne(mbox);
+            }
+        }
+    }
+
+    fn mergeOne(&mut self, mbox: &MailboxInfo<'_>) {
+        let mut mbox = mbox.Owned();
+
+        match self
+            .Mailboxes
+            .iter()
+            .position(|m| sameName(&m.Name, &mbox.Name))
+        {
+            Some(i) => {
+                // A plain LIST doesn't say whether the mailbox is subscribed
+                if self.Mailboxes[i].Subscribed() && !mbox.Subscribed() {
+                    mbox.Attributes.to_mut().push(MailboxAttribute::Subscribed);
+                }
+                self.Mailboxes.to_mut()[i] = mbox;
+            }
+            None => self.Mailboxes.to_mut().push(mbox),
+        }
+    }
+
+    /// Records a newly created mailbox. A trailing hierarchy delimiter, used
+    /// to declare a mailbox meant to hold others, is not part of the name.
+    pub fn Created(&mut self, name: &str, attributes: &[MailboxAttribute]) {
+        let delimiter = self.Delimiter().unwrap_or_default().to_string();
+        let name = match delimiter.chars().next() {
+            Some(d) => name.trim_end_matches(d),
+            None => name,
+        };
+
+        if self.Get(name).is_none() {
+            self.Mailboxes.to_mut().push(MailboxInfo {
+                Attributes: Cow::Owned(attributes.to_vec()),
+                Delimiter: Cow::Owned(delimiter),
+                Name: Cow::Owned(name.to_string()),
+                ..Default::default()
+            });
+        }
+    }
+
+    /// Forgets a deleted mailbox. Its inferiors are left alone, as the server
+    /// keeps them.
+    pub fn Deleted(&mut self, name: &str) {
+        self.Mailboxes.to_mut().retain(|m| !sameName(&m.Name, name));
+    }
+
+    /// Renames a mailbox along with its inferiors. Renaming INBOX moves its
+    /// messages to a new mailbox and leaves INBOX in place.
+    pub fn Renamed(&mut self, existing: &str, new: &str) {
+        if existing.eq_ignore_ascii_case("INBOX") {
+            self.Created(new, &[]);
+            return;
+        }
+
+        for mbox in self.Mailboxes.to_mut().iter_mut() {
+            if sameName(&mbox.Name, existing) {
+                mbox.Name = Cow::Owned(new.to_string());
+            } else if !mbox.Delimiter.trim().is_empty() {
+                let prefix = format!("{}{}", existing, mbox.Delimiter);
+                if let Some(rest) = mbox.Name.strip_prefix(prefix.as_str()) {
+                    mbox.Name = Cow::Owned(format!("{}{}{}", new, mbox.Delimiter, rest));
+                }
+            }
+        }
+    }
+
+    pub fn SetSubscribed(&mut self, name: &str, subscribed: bool) {
+        if let Some(mbox) = self
+            .Mailboxes
+            .to_mut()
+            .iter_mut()
+            .find(|m| sameName(&m.Name, name))
+        {
+            mbox.Attributes
+                .to_mut()
+                .retain(|a| a != &MailboxAttribute::Subscribed);
+            if subscribed {
+                mbox.Attributes.to_mut().push(MailboxAttribute::Subscribed);
+            }
+        }
+    }
+}
+
+/// Compares mailbox names, which are case-sensitive except for INBOX
+/// (RFC 3501).
+fn sameName(a: &str, b: &str) -> bool {
+    a == b || (a.eq_ignore_ascii_case("INBOX") && b.eq_ignore_ascii_case("INBOX"))
+}
+
+impl<'m> MailboxInfo<'m> {
+    pub fn Owned<'o>(&self) -> MailboxInfo<'o> {
+        MailboxInfo {
+            Attributes: Cow::Owned(self.Attributes.to_vec()),
+            Delimiter: Cow::Owned(self.Delimiter.to_string()),
+            Name: Cow::Owned(self.Name.to_string()),
+            ChildInfo: self
+                .ChildInfo
+                .iter()
+                .map(|a| Cow::Owned(a.to_string()))
+                .collect(),
+            OldName: self.OldName.as_ref().map(|n| Cow::Owned(n.to_string())),
+            Status: self.Status.as_ref().map(StatusInfo::Owned),
+        }
+    }
+
+    pub fn Has(&self, attribute: &MailboxAttribute) -> bool {
+        self.Attributes.contains(attribute)
+    }
+
+    pub fn Subscribed(&self) -> bool {
+        self.Has(&MailboxAttribute::Subscribed)
+    }
+}
+
 #[async_trait]
 impl<'s> Handler<'s> for List<'s> {
     async fn Handle(&mut self, resp: &mut Resp<'s>) -> io::Result<()> {
+        if let Some(status) = status::ParseStatus(resp, self.Utf8).await? {
+            if let Some(mbox) = self
+                .Mailboxes
+                .to_mut()
+                .iter_mut()
+                .find(|m| sameName(&m.Name, &status.Name))
+            {
+                mbox.Status = Some(status);
+            }
+            return Ok(());
+        }
+
         if let Resp::DataResp(resp) = resp {
             let (name, fields) = response::ParseNamedResp(resp).await?;
-            if name == "LIST" {
-                self.Mailboxes.to_mut().push(MailboxInfo::Parse(fields)?);
+            match name.as_ref() {
+                "LIST" => self
+                    .Mailboxes
+                    .to_mut()
+                    .push(MailboxInfo::Parse(fields, self.Utf8)?),
+                "LSUB" => {
+                    let mut mbox = MailboxInfo::Parse(fields, self.Utf8)?;
+                    if !mbox.Subscribed() {
+                        mbox.Attributes.to_mut().push(MailboxAttribute::Subscribed);
+                    }
+                    self.Mailboxes.to_mut().push(mbox);
+                }
+                _ => {}
             }
         }
 
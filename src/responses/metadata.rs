@@ -0,0 +1,92 @@
+use std::borrow::Cow;
+
+use super::Handler;
+use crate::{
+    read::TY,
+    response::{self, Resp},
+    utf7,
+};
+use async_trait::async_trait;
+use futures_lite::io;
+
+/// The `* METADATA` responses (RFC 5464).
+#[derive(Debug, Default, Clone)]
+pub struct Metadata<'s> {
+    /// Entries returned by GETMETADATA.
+    pub Entries: Cow<'s, [MetadataEntry<'s>]>,
+    /// Entries the server reported as changed, without their values, as
+    /// `(mailbox, entry)`.
+    pub Changed: Cow<'s, [(Cow<'s, str>, Cow<'s, str>)]>,
+    /// Mailbox names are UTF-8 once UTF8=ACCEPT is enabled.
+    pub Utf8: bool,
+}
+
+#[derive(Debug, Default, Clone, PartialEq, Eq)]
+pub struct MetadataEntry<'m> {
+    /// The mailbox, empty for a server entry.
+    pub Mailbox: Cow<'m, str>,
+    pub Entry: Cow<'m, str>,
+    /// `None` when the entry has no value.
+    pub Value: Option<Cow<'m, str>>,
+}
+
+#[async_trait]
+impl<'s> Handler<'s> for Metadata<'s> {
+    async fn Handle(&mut self, resp: &mut Resp<'s>) -> io::Result<()> {
+        let resp = match resp {
+            Resp::DataResp(resp) => resp,
+            _ => return Ok(()),
+        };
+
+        let (name, fields) = response::ParseNamedResp(resp).await?;
+        if name != "METADATA" {
+            return Ok(());
+        }
+
+        let (mailbox, rest) = match fields.split_first() {
+            Some((TY::Str(mailbox), rest)) => (utf7::DecodeName(mailbox, self.Utf8), rest),
+            _ => {
+                return Err(io::Error::new(
+                    io::ErrorKind::Other,
+                    "invalid METADATA response",
+                ))
+            }
+        };
+
+        match rest {
+            // Entries with their values
+            [TY::List(entries)] => {
+                for pair in entries.chunks(2) {
+                    let (entry, value) = match pair {
+                        [TY::Str(entry), value] => (entry, value),
+                        _ => {
+                            return Err(io::Error::new(
+                                io::ErrorKind::Other,
+                                "METADATA entry has no value",
+                            ))
+                        }
+                    };
+
+                    self.Entries.to_mut().push(MetadataEntry {
+                        Mailbox: mailbox.clone(),
+                        Entry: entry.clone(),
+                        Value: match value {
+                            TY::Str(value) => Some(value.clone()),
+                            _ => None,
+                        },
+                    });
+                }
+            }
+            // Unsolicited list of changed entries
+            entries => {
+                for entry in entries.iter().filter_map(TY::AsStr) {
+                    self.Changed
+                        .to_mut()
+                        .push((mailbox.clone(), Cow::Owned(entry.to_string())));
+                }
+            }
+        }
+
+        Ok(())
+    }
+}
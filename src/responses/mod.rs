@@ -14,5 +14,53 @@ pub use select::Select;
 pub mod login;
 pub use login::Login;
 
+pub mod none;
+pub use none::None;
+
 pub mod list;
-pub use list::List;
+pub use list::{List, MailboxAttribute, MailboxInfo};
+
+pub mod fetch;
+pub use fetch::{Fetch, Message};
+
+pub mod expunge;
+pub use expunge::Expunge;
+
+pub mod status;
+pub use status::{Status, StatusInfo};
+
+pub mod tree;
+pub use tree::{MailboxNode, MailboxTree};
+
+pub mod enable;
+pub use enable::Enable;
+
+pub mod namespace;
+pub use namespace::{Namespace, NamespaceDesc};
+
+pub mod id;
+pub use id::Id;
+
+pub mod quota;
+pub use quota::{Quota, QuotaInfo, QuotaUsage};
+
+pub mod acl;
+pub use acl::{Acl, ListRightsInfo};
+
+pub mod metadata;
+pub use metadata::{Metadata, MetadataEntry};
+
+pub mod search;
+pub use search::Search;
+
+pub mod sort;
+pub use sort::Sort;
+
+pub mod thread;
+pub use thread::{Thread, ThreadNode};
+
+pub mod envelope;
+pub use envelope::{Address, Envelope};
+
+pub mod resync;
+pub use resync::Resync;
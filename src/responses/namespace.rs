@@ -0,0 +1,109 @@
+use std::borrow::Cow;
+
+use super::Handler;
+use crate::{
+    read::TY,
+    response::{self, Resp},
+    utf7,
+};
+use async_trait::async_trait;
+use futures_lite::io;
+
+/// The `* NAMESPACE personal other-users shared` response. A kind of
+/// namespace the server doesn't have is empty.
+#[derive(Debug, Default, Clone, PartialEq, Eq)]
+pub struct Namespace<'s> {
+    pub Personal: Cow<'s, [NamespaceDesc<'s>]>,
+    pub OtherUsers: Cow<'s, [NamespaceDesc<'s>]>,
+    pub Shared: Cow<'s, [NamespaceDesc<'s>]>,
+    /// Prefixes are UTF-8 rather than modified UTF-7, set once UTF8=ACCEPT
+    /// is enabled.
+    pub Utf8: bool,
+}
+
+#[derive(Debug, Default, Clone, PartialEq, Eq)]
+pub struct NamespaceDesc<'n> {
+    /// The prefix of the mailbox names in the namespace, e.g. `#shared/`.
+    pub Prefix: Cow<'n, str>,
+    /// The hierarchy delimiter, `None` for a flat namespace.
+    pub Delimiter: Option<Cow<'n, str>>,
+    /// Extension data: a name and its values, e.g.
+    /// `("TRANSLATION", ["Shared Folders"])`.
+    pub Extensions: Cow<'n, [(Cow<'n, str>, Cow<'n, [Cow<'n, str>]>)]>,
+}
+
+impl<'n> NamespaceDesc<'n> {
+    fn Parse(desc: &TY<'n>, utf8: bool) -> io::Result<NamespaceDesc<'n>> {
+        let invalid = || io::Error::new(io::ErrorKind::Other, "invalid namespace description");
+
+        let (prefix, delimiter, extensions) = match desc.AsList() {
+            Some([TY::Str(prefix), delimiter, extensions @ ..]) => (prefix, delimiter, extensions),
+            _ => return Err(invalid()),
+        };
+
+        let mut ns = NamespaceDesc {
+            Prefix: utf7::DecodeName(prefix, utf8),
+            Delimiter: delimiter.AsStr().map(|d| Cow::Owned(d.to_string())),
+            ..Default::default()
+        };
+
+        for pair in extensions.chunks(2) {
+            match pair {
+                [TY::Str(name), TY::List(values)] => ns.Extensions.to_mut().push((
+                    name.clone(),
+                    values
+                        .iter()
+                        .filter_map(|v| v.AsStr().map(|v| Cow::Owned(v.to_string())))
+                        .collect(),
+                )),
+                _ => return Err(invalid()),
+            }
+        }
+
+        Ok(ns)
+    }
+}
+
+impl<'s> Namespace<'s> {
+    /// Parses the fields following `NAMESPACE`. `utf8` is set once
+    /// UTF8=ACCEPT is enabled.
+    pub fn Parse(fields: &[TY<'s>], utf8: bool) -> io::Result<Namespace<'s>> {
+        let parse = |field: Option<&TY<'s>>| -> io::Result<Cow<'s, [NamespaceDesc<'s>]>> {
+            match field {
+                Some(TY::Nil) => Ok(Default::default()),
+                Some(TY::List(descs)) => descs
+                    .iter()
+                    .map(|d| NamespaceDesc::Parse(d, utf8))
+                    .collect(),
+                _ => Err(io::Error::new(
+                    io::ErrorKind::Other,
+                    "invalid NAMESPACE response",
+                )),
+            }
+        };
+
+        Ok(Namespace {
+            Personal: parse(fields.get(0))?,
+            OtherUsers: parse(fields.get(1))?,
+            Shared: parse(fields.get(2))?,
+            Utf8: utf8,
+        })
+    }
+}
+
+#[async_trait]
+impl<'s> Handler<'s> for Namespace<'s> {
+    async fn Handle(&mut self, resp: &mut Resp<'s>) -> io::Result<()> {
+        let resp = match resp {
+            Resp::DataResp(resp) => resp,
+            _ => return Ok(()),
+        };
+
+        let (name, fields) = response::ParseNamedResp(resp).await?;
+        if name == "NAMESPACE" {
+            *self = Namespace::Parse(&fields, self.Utf8)?;
+        }
+
+        Ok(())
+    }
+}
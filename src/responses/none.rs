@@ -0,0 +1,16 @@
+use super::Handler;
+use crate::response::Resp;
+use async_trait::async_trait;
+use futures_lite::io;
+
+/// Ignores every untagged response, for commands that only report success
+/// or failure in the tagged one.
+#[derive(Debug, Default, Clone, Copy)]
+pub struct None;
+
+#[async_trait]
+impl<'s> Handler<'s> for None {
+    async fn Handle(&mut self, _: &mut Resp<'s>) -> io::Result<()> {
+        Ok(())
+    }
+}
@@ -0,0 +1,115 @@
+use std::borrow::Cow;
+
+use super::Handler;
+use crate::{
+    commands::QuotaResource,
+    read::TY,
+    response::{self, Resp},
+    utf7,
+};
+use async_trait::async_trait;
+use futures_lite::io;
+
+/// The `* QUOTAROOT` and `* QUOTA` responses of GETQUOTA, GETQUOTAROOT and
+/// SETQUOTA.
+#[derive(Debug, Default, Clone)]
+pub struct Quota<'s> {
+    /// The mailbox of a `* QUOTAROOT` response.
+    pub Mailbox: Option<Cow<'s, str>>,
+    /// The quota roots the mailbox belongs to.
+    pub Roots: Cow<'s, [Cow<'s, str>]>,
+    pub Quotas: Cow<'s, [QuotaInfo<'s>]>,
+    /// The mailbox name is UTF-8, not modified UTF-7, once UTF8=ACCEPT is
+    /// enabled.
+    pub Utf8: bool,
+}
+
+/// The limits of a quota root.
+#[derive(Debug, Default, Clone, PartialEq, Eq)]
+pub struct QuotaInfo<'q> {
+    pub Root: Cow<'q, str>,
+    pub Resources: Cow<'q, [QuotaUsage]>,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct QuotaUsage {
+    pub Resource: QuotaResource,
+    pub Usage: u64,
+    pub Limit: u64,
+}
+
+impl<'q> QuotaInfo<'q> {
+    /// Parses the fields following `QUOTA`: the root, then the list of
+    /// resource names, usages and limits.
+    pub fn Parse(fields: &[TY<'q>]) -> io::Result<QuotaInfo<'q>> {
+        let invalid = || io::Error::new(io::ErrorKind::Other, "invalid QUOTA response");
+
+        let (root, resources) = match fields {
+            [TY::Str(root), TY::List(resources)] => (root, resources),
+            _ => return Err(invalid()),
+        };
+
+        let mut quota = QuotaInfo {
+            Root: root.clone(),
+            ..Default::default()
+        };
+
+        for triple in resources.chunks(3) {
+            match triple {
+                [TY::Str(name), usage, limit] => quota.Resources.to_mut().push(QuotaUsage {
+                    Resource: name
+                        .parse()
+                        .unwrap_or_else(|_| QuotaResource::Other(name.to_string())),
+                    Usage: usage.Parse().ok_or_else(invalid)?,
+                    Limit: limit.Parse().ok_or_else(invalid)?,
+                }),
+                _ => return Err(invalid()),
+            }
+        }
+
+        Ok(quota)
+    }
+
+    pub fn Get(&self, resource: &QuotaResource) -> Option<&QuotaUsage> {
+        self.Resources.iter().find(|r| &r.Resource == resource)
+    }
+}
+
+impl QuotaUsage {
+    /// Reports whether the usage reached the limit.
+    pub fn Exceeded(&self) -> bool {
+        self.Usage >= self.Limit
+    }
+}
+
+#[async_trait]
+impl<'s> Handler<'s> for Quota<'s> {
+    async fn Handle(&mut self, resp: &mut Resp<'s>) -> io::Result<()> {
+        let resp = match resp {
+            Resp::DataResp(resp) => resp,
+            _ => return Ok(()),
+        };
+
+        let (name, fields) = response::ParseNamedResp(resp).await?;
+        match name.as_ref() {
+            "QUOTAROOT" => {
+                let (mailbox, roots) = fields.split_first().ok_or_else(|| {
+                    io::Error::new(io::ErrorKind::Other, "invalid QUOTAROOT response")
+                })?;
+
+                self.Mailbox = match mailbox {
+                    TY::Str(mailbox) => Some(utf7::DecodeName(mailbox, self.Utf8)),
+                    _ => None,
+                };
+                self.Roots = roots
+                    .iter()
+                    .filter_map(|r| r.AsStr().map(|r| Cow::Owned(r.to_string())))
+                    .collect();
+            }
+            "QUOTA" => self.Quotas.to_mut().push(QuotaInfo::Parse(&fields)?),
+            _ => {}
+        }
+
+        Ok(())
+    }
+}
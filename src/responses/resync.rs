@@ -0,0 +1,78 @@
+use std::borrow::Cow;
+
+use super::{fetch, select::MailboxStatus, Handler, Message, Select};
+use crate::{
+    read::TY,
+    response::{self, Resp},
+    SequenceSet,
+};
+use async_trait::async_trait;
+use futures_lite::io;
+
+/// What changed in a mailbox since a previous session (RFC 7162): the
+/// messages whose flags changed and the UIDs that were expunged. Filled by
+/// a SELECT with QRESYNC, which also sets `Mailbox`, or by a UID FETCH with
+/// CHANGEDSINCE and VANISHED.
+#[derive(Debug, Default, Clone)]
+pub struct Resync<'s> {
+    pub Mailbox: MailboxStatus<'s>,
+    pub Changed: Cow<'s, [Message<'s>]>,
+    pub Vanished: SequenceSet,
+}
+
+/// Parses a `* VANISHED [(EARLIER)] uid-set` response into the UIDs and
+/// whether they are `EARLIER` ones, expunged before the command rather
+/// than while it ran. Returns `None` for any other response.
+pub async fn ParseVanished(resp: &Resp<'_>) -> io::Result<Option<(SequenceSet, bool)>> {
+    let resp = match resp {
+        Resp::DataResp(resp) => resp,
+        _ => return Ok(None),
+    };
+
+    let (name, fields) = response::ParseNamedResp(resp).await?;
+    if name != "VANISHED" {
+        return Ok(None);
+    }
+
+    let (earlier, set) = match fields.as_ref() {
+        [TY::List(tags), set] => (
+            tags.iter()
+                .filter_map(TY::AsStr)
+                .any(|t| t.eq_ignore_ascii_case("EARLIER")),
+            set,
+        ),
+        [set] => (false, set),
+        _ => (false, &TY::Nil),
+    };
+
+    match set.Parse::<SequenceSet>() {
+        Some(set) => Ok(Some((set, earlier))),
+        None => Err(io::Error::new(
+            io::ErrorKind::Other,
+            "invalid VANISHED response",
+        )),
+    }
+}
+
+#[async_trait]
+impl<'s> Handler<'s> for Resync<'s> {
+    async fn Handle(&mut self, resp: &mut Resp<'s>) -> io::Result<()> {
+        if let Some(msg) = fetch::ParseFetch(resp).await? {
+            self.Changed.to_mut().push(msg);
+            return Ok(());
+        }
+
+        if let Some((uids, _)) = ParseVanished(resp).await? {
+            self.Vanished = self.Vanished.Union(&uids);
+            return Ok(());
+        }
+
+        let mut select = Select {
+            Mailbox: std::mem::take(&mut self.Mailbox),
+        };
+        let handled = select.Handle(resp).await;
+        self.Mailbox = select.Mailbox;
+
+        handled
+    }
+}
@@ -0,0 +1,56 @@
+use std::borrow::Cow;
+
+use super::Handler;
+use crate::{
+    read::TY,
+    response::{self, Resp},
+};
+use async_trait::async_trait;
+use futures_lite::io;
+
+/// The `* SEARCH` response: the matching message numbers, or UIDs for UID
+/// SEARCH, in ascending order.
+#[derive(Debug, Default, Clone)]
+pub struct Search<'s> {
+    pub Ids: Cow<'s, [u32]>,
+    /// The highest mod-sequence of the matching messages, sent when the
+    /// search used MODSEQ (RFC 7162).
+    pub ModSeq: Option<u64>,
+}
+
+#[async_trait]
+impl<'s> Handler<'s> for Search<'s> {
+    async fn Handle(&mut self, resp: &mut Resp<'s>) -> io::Result<()> {
+        let resp = match resp {
+            Resp::DataResp(resp) => resp,
+            _ => return Ok(()),
+        };
+
+        let (name, fields) = response::ParseNamedResp(resp).await?;
+        if name == "SEARCH" {
+            self.ModSeq = parseIds(&fields, self.Ids.to_mut())?.or(self.ModSeq);
+        }
+
+        Ok(())
+    }
+}
+
+/// Parses the ids of a SEARCH or SORT response into `ids` and returns the
+/// trailing `(MODSEQ n)`, if any.
+pub(super) fn parseIds(fields: &[TY], ids: &mut Vec<u32>) -> io::Result<Option<u64>> {
+    let mut modSeq = None;
+    for id in fields.iter() {
+        if let Some([TY::Str(name), value]) = id.AsList() {
+            if name.eq_ignore_ascii_case("MODSEQ") {
+                modSeq = value.Parse();
+                continue;
+            }
+        }
+
+        ids.push(
+            id.Parse()
+                .ok_or_else(|| io::Error::new(io::ErrorKind::Other, "invalid search result"))?,
+        );
+    }
+    Ok(modSeq)
+}
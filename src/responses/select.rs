@@ -2,11 +2,13 @@ use std::borrow::Cow;
 
 use super::Handler;
 use crate::{
+    flag::Flag,
     read::TY,
     response::{self, Resp, StatusResp},
+    Rights,
 };
 use async_trait::async_trait;
-use futures_lite::{io, FutureExt};
+use futures_lite::io;
 
 #[derive(Debug, Default, Clone)]
 pub struct Select<'s> {
@@ -18,20 +20,38 @@ pub struct MailboxStatus<'m> {
     pub Name: Cow<'m, str>,
     pub ReadOnly: bool,
     pub Items: Cow<'m, [Cow<'m, str>]>,
-    pub Flags: Cow<'m, [Cow<'m, str>]>,
+    pub Flags: Cow<'m, [Flag]>,
     pub UnseenSeqNum: usize,
-    pub PermanentFlags: Cow<'m, [Cow<'m, str>]>,
+    pub PermanentFlags: Cow<'m, [Flag]>,
     pub UidNext: usize,
     pub UidValidity: usize,
     pub Messages: usize,
     pub Recents: usize,
+    /// Set when the server sent `[UIDNOTSTICKY]`: UIDs in this mailbox are not
+    /// kept across sessions and must not be cached.
+    pub UidNotSticky: bool,
+    /// The rights of the user on the mailbox (RFC 4314), `None` when the
+    /// server doesn't support ACL.
+    pub MyRights: Option<Rights>,
+    /// The highest mod-sequence of the mailbox (RFC 7162), `None` when the
+    /// server sent `[NOMODSEQ]` or doesn't support CONDSTORE.
+    pub HighestModSeq: Option<u64>,
+}
+
+impl<'m> MailboxStatus<'m> {
+    /// Reports whether the user may do what `rights` allow, e.g.
+    /// `Rights::DeleteMessages` before marking messages \Deleted. Always true
+    /// when the rights are unknown.
+    pub fn Allows(&self, rights: Rights) -> bool {
+        self.MyRights
+            .map_or(true, |r| r.Expanded().Contains(rights))
+    }
 }
 
 #[async_trait]
 impl<'s> Handler<'s> for Select<'s> {
     async fn Handle(&mut self, resp: &mut Resp<'s>) -> io::Result<()> {
         match resp {
-            Resp::ContReq(resp) => todo!(),
             Resp::StatResp(StatusResp {
                 Code, Arguments, ..
             }) => match Code.as_ref() {
@@ -49,7 +69,7 @@ impl<'s> Handler<'s> for Select<'s> {
                             None
                         }
                     }) {
-                        self.Mailbox.PermanentFlags = list.to_owned();
+                        self.Mailbox.PermanentFlags = Flag::ParseList(list);
                     }
                 }
                 "UIDNEXT" => {
@@ -64,13 +84,21 @@ impl<'s> Handler<'s> for Select<'s> {
                         _ => 0,
                     };
                 }
-                a => {
-                    dbg!(a);
-                    todo!()
+                "UIDNOTSTICKY" => {
+                    self.Mailbox.UidNotSticky = true;
+                }
+                "HIGHESTMODSEQ" => {
+                    self.Mailbox.HighestModSeq = Arguments.get(0).and_then(TY::Parse);
+                }
+                "NOMODSEQ" => {
+                    self.Mailbox.HighestModSeq = None;
                 }
+                "READ-ONLY" => self.Mailbox.ReadOnly = true,
+                "READ-WRITE" => self.Mailbox.ReadOnly = false,
+                _ => {}
             },
             Resp::DataResp(resp) => {
-                let (name, fields) = response::ParseNamedResp(&resp).await?;
+                let (name, fields) = response::ParseNamedResp(resp).await?;
                 match name.as_ref() {
                     "FLAGS" => {
                         if let Some(list) = fields.get(0).map_or(None, |a| {
@@ -80,7 +108,7 @@ impl<'s> Handler<'s> for Select<'s> {
                                 None
                             }
                         }) {
-                            self.Mailbox.Flags = list.to_owned();
+                            self.Mailbox.Flags = Flag::ParseList(list);
                         }
                     }
                     "EXISTS" => {
@@ -95,9 +123,10 @@ impl<'s> Handler<'s> for Select<'s> {
                             _ => 0,
                         };
                     }
-                    _ => todo!(),
+                    _ => {}
                 }
             }
+            _ => {}
         };
 
         Ok(())
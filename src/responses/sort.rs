@@ -0,0 +1,33 @@
+use std::borrow::Cow;
+
+use super::Handler;
+use crate::response::{self, Resp};
+use async_trait::async_trait;
+use futures_lite::io;
+
+/// The `* SORT` response: message numbers, or UIDs for UID SORT, in the
+/// requested order.
+#[derive(Debug, Default, Clone)]
+pub struct Sort<'s> {
+    pub Ids: Cow<'s, [u32]>,
+    /// The highest mod-sequence of the messages, sent when the search
+    /// used MODSEQ (RFC 7162).
+    pub ModSeq: Option<u64>,
+}
+
+#[async_trait]
+impl<'s> Handler<'s> for Sort<'s> {
+    async fn Handle(&mut self, resp: &mut Resp<'s>) -> io::Result<()> {
+        let resp = match resp {
+            Resp::DataResp(resp) => resp,
+            _ => return Ok(()),
+        };
+
+        let (name, fields) = response::ParseNamedResp(resp).await?;
+        if name == "SORT" {
+            self.ModSeq = super::search::parseIds(&fields, self.Ids.to_mut())?.or(self.ModSeq);
+        }
+
+        Ok(())
+    }
+}
@@ -0,0 +1,120 @@
+use std::borrow::Cow;
+
+use super::Handler;
+use crate::{
+    read::TY,
+    response::{self, Resp},
+    utf7,
+};
+use async_trait::async_trait;
+use futures_lite::io;
+
+#[derive(Debug, Default, Clone)]
+pub struct Status<'s> {
+    pub Mailbox: StatusInfo<'s>,
+    /// Whether UTF8=ACCEPT is enabled, the name is not decoded then.
+    pub Utf8: bool,
+}
+
+/// The data of a `* STATUS name (...)` response. Items that were not
+/// requested are left as `None`.
+#[derive(Debug, Default, Clone, PartialEq, Eq)]
+pub struct StatusInfo<'m> {
+    pub Name: Cow<'m, str>,
+    pub Messages: Option<u32>,
+    pub Recent: Option<u32>,
+    pub UidNext: Option<u32>,
+    pub UidValidity: Option<u32>,
+    pub Unseen: Option<u32>,
+    pub HighestModSeq: Option<u64>,
+    pub Size: Option<u64>,
+    pub Deleted: Option<u32>,
+}
+
+impl<'m> StatusInfo<'m> {
+    pub fn Owned<'o>(&self) -> StatusInfo<'o> {
+        StatusInfo {
+            Name: Cow::Owned(self.Name.to_string()),
+            Messages: self.Messages,
+            Recent: self.Recent,
+            UidNext: self.UidNext,
+            UidValidity: self.UidValidity,
+            Unseen: self.Unseen,
+            HighestModSeq: self.HighestModSeq,
+            Size: self.Size,
+            Deleted: self.Deleted,
+        }
+    }
+
+    /// Parses the fields following `STATUS`: the mailbox name, then the list
+    /// of item names and values. `utf8` is set once UTF8=ACCEPT is enabled.
+    pub fn Parse(fields: &[TY<'m>], utf8: bool) -> io::Result<StatusInfo<'m>> {
+        let (name, items) = match fields {
+            [TY::Str(name), TY::List(items)] => (name, items),
+            _ => {
+                return Err(io::Error::new(
+                    io::ErrorKind::Other,
+                    "invalid STATUS response",
+                ));
+            }
+        };
+
+        let mut status = StatusInfo {
+            Name: utf7::DecodeName(name, utf8),
+            ..Default::default()
+        };
+
+        for pair in items.chunks(2) {
+            let (key, value) = match pair {
+                [key, value] => (key.AsStr().unwrap_or_default(), value),
+                _ => {
+                    return Err(io::Error::new(
+                        io::ErrorKind::Other,
+                        "STATUS item has no value",
+                    ));
+                }
+            };
+
+            match key.to_uppercase().as_str() {
+                "MESSAGES" => status.Messages = value.Parse(),
+                "RECENT" => status.Recent = value.Parse(),
+                "UIDNEXT" => status.UidNext = value.Parse(),
+                "UIDVALIDITY" => status.UidValidity = value.Parse(),
+                "UNSEEN" => status.Unseen = value.Parse(),
+                "HIGHESTMODSEQ" => status.HighestModSeq = value.Parse(),
+                "SIZE" => status.Size = value.Parse(),
+                "DELETED" => status.Deleted = value.Parse(),
+                _ => {}
+            }
+        }
+
+        Ok(status)
+    }
+}
+
+/// Parses an untagged `* STATUS` response, returns `None` for any other
+/// response.
+pub async fn ParseStatus<'a>(resp: &Resp<'a>, utf8: bool) -> io::Result<Option<StatusInfo<'a>>> {
+    let resp = match resp {
+        Resp::DataResp(resp) => resp,
+        _ => return Ok(None),
+    };
+
+    let (name, fields) = response::ParseNamedResp(resp).await?;
+    if name != "STATUS" {
+        return Ok(None);
+    }
+
+    Ok(Some(StatusInfo::Parse(&fields, utf8)?))
+}
+
+#[async_trait]
+impl<'s> Handler<'s> for Status<'s> {
+    async fn Handle(&mut self, resp: &mut Resp<'s>) -> io::Result<()> {
+        if let Some(status) = ParseStatus(resp, self.Utf8).await? {
+            self.Mailbox = status;
+        }
+
+        Ok(())
+    }
+}
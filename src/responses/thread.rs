@@ -0,0 +1,91 @@
+use super::Handler;
+use crate::{
+    read::TY,
+    response::{self, Resp},
+};
+use async_trait::async_trait;
+use futures_lite::io;
+
+/// A message of a thread and the replies to it.
+#[derive(Debug, Default, Clone, PartialEq, Eq)]
+pub struct ThreadNode {
+    /// The message number, or UID for UID THREAD. `None` for the missing
+    /// parent of messages known to be in the same thread, as in `((3)(5))`.
+    pub Id: Option<u32>,
+    pub Children: Vec<ThreadNode>,
+}
+
+impl ThreadNode {
+    /// The IDs of the node and everything under it, parents before their
+    /// children.
+    pub fn Ids(&self) -> Vec<u32> {
+        let mut ids = Vec::new();
+        self.walk(&mut ids);
+        ids
+    }
+
+    fn walk(&self, ids: &mut Vec<u32>) {
+        ids.extend(self.Id);
+        for child in self.Children.iter() {
+            child.walk(ids);
+        }
+    }
+}
+
+/// The `* THREAD` response, one root per thread.
+#[derive(Debug, Default, Clone)]
+pub struct Thread {
+    pub Threads: Vec<ThreadNode>,
+}
+
+#[async_trait]
+impl<'s> Handler<'s> for Thread {
+    async fn Handle(&mut self, resp: &mut Resp<'s>) -> io::Result<()> {
+        let resp = match resp {
+            Resp::DataResp(resp) => resp,
+            _ => return Ok(()),
+        };
+
+        let (name, fields) = response::ParseNamedResp(resp).await?;
+        if name == "THREAD" {
+            for thread in fields.iter() {
+                let thread = thread.AsList().ok_or_else(invalid)?;
+                self.Threads.push(parseThread(thread)?);
+            }
+        }
+
+        Ok(())
+    }
+}
+
+fn invalid() -> io::Error {
+    io::Error::new(io::ErrorKind::Other, "invalid THREAD response")
+}
+
+/// Parses the contents of a thread list. In `(1 2 (3)(4 5))` each number
+/// is the parent of the next, and the lists that follow are the branches
+/// under the last one.
+fn parseThread(items: &[TY<'_>]) -> io::Result<ThreadNode> {
+    match items.split_first() {
+        Some((TY::Str(id), rest)) => Ok(ThreadNode {
+            Id: Some(id.parse().map_err(|_| invalid())?),
+            Children: match rest.first() {
+                None => Vec::new(),
+                Some(TY::Str(_)) => vec![parseThread(rest)?],
+                Some(_) => parseBranches(rest)?,
+            },
+        }),
+        Some((TY::List(_), _)) => Ok(ThreadNode {
+            Id: None,
+            Children: parseBranches(items)?,
+        }),
+        _ => Err(invalid()),
+    }
+}
+
+fn parseBranches(items: &[TY<'_>]) -> io::Result<Vec<ThreadNode>> {
+    items
+        .iter()
+        .map(|item| parseThread(item.AsList().ok_or_else(invalid)?))
+        .collect()
+}
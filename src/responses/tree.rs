@@ -0,0 +1,178 @@
+use super::{List, MailboxAttribute, MailboxInfo};
+
+/// The mailbox hierarchy described by LIST responses.
+///
+/// Names are split on each mailbox's own hierarchy delimiter. Levels the
+/// server did not list, e.g. `Work` when only `Work/2024` was returned, are
+/// added as nodes without `Info`.
+#[derive(Debug, Default, Clone)]
+pub struct MailboxTree<'t> {
+    pub Roots: Vec<MailboxNode<'t>>,
+}
+
+#[derive(Debug, Default, Clone)]
+pub struct MailboxNode<'t> {
+    /// The last component of the path.
+    pub Name: String,
+    /// The full mailbox name.
+    pub Path: String,
+    /// The hierarchy delimiter, empty for a flat name.
+    pub Delimiter: String,
+    /// What LIST said about the mailbox, `None` for synthesized levels.
+    pub Info: Option<MailboxInfo<'t>>,
+    pub Children: Vec<MailboxNode<'t>>,
+}
+
+impl<'t> MailboxTree<'t> {
+    pub fn Build(list: &List<'_>) -> MailboxTree<'t> {
+        let mut tree = MailboxTree::default();
+        for mbox in list.Mailboxes.iter() {
+            tree.Insert(mbox);
+        }
+        tree
+    }
+
+    /// Adds a mailbox, creating the levels above it as needed. A mailbox
+    /// already in the tree, listed or synthesized, gets the new info.
+    pub fn Insert(&mut self, mbox: &MailboxInfo<'_>) {
+        let delimiter = mbox.Delimiter.trim();
+        let parts: Vec<&str> = if delimiter.is_empty() {
+            vec![mbox.Name.as_ref()]
+        } else {
+            mbox.Name.split(delimiter).collect()
+        };
+
+        let mut nodes = &mut self.Roots;
+        let mut path = String::new();
+        for (i, part) in parts.iter().enumerate() {
+            if i > 0 {
+                path.push_str(delimiter);
+            }
+            path.push_str(part);
+
+            let pos = match nodes.iter().position(|n| n.Path == path) {
+                Some(pos) => pos,
+                None => {
+                    nodes.push(MailboxNode {
+                        Name: part.to_string(),
+                        Path: path.clone(),
+                        Delimiter: delimiter.to_string(),
+                        ..Default::default()
+                    });
+                    nodes.len() - 1
+                }
+            };
+
+            let node = &mut nodes[pos];
+            if i == parts.len() - 1 {
+                node.Info = Some(mbox.Owned());
+            }
+            nodes = &mut node.Children;
+        }
+    }
+
+    pub fn Get(&self, path: &str) -> Option<&MailboxNode<'t>> {
+        find(&self.Roots, path)
+    }
+
+    /// The node directly above `path`, `None` for a top-level mailbox.
+    pub fn Parent(&self, path: &str) -> Option<&MailboxNode<'t>> {
+        self.Nodes()
+            .into_iter()
+            .find(|n| n.Children.iter().any(|c| c.Path == path))
+    }
+
+    /// Every node of the tree, parents before their children.
+    pub fn Nodes(&self) -> Vec<&MailboxNode<'t>> {
+        let mut nodes = Vec::new();
+        for root in self.Roots.iter() {
+            root.walk(&mut nodes);
+        }
+        nodes
+    }
+
+    /// The names each mailbox of the subtree at `existing` gets when it is
+    /// renamed to `new`, in the order they appear in the tree. Only listed
+    /// mailboxes are included.
+    ///
+    /// The server renames inferiors itself; this is for updating state kept
+    /// about them, or for moving a subtree on a server that doesn't.
+    pub fn PlanRename(&self, existing: &str, new: &str) -> Vec<(String, String)> {
+        let node = match self.Get(existing) {
+            Some(node) => node,
+            None => return Vec::new(),
+        };
+
+        node.Descendants()
+            .into_iter()
+            .filter(|n| n.Info.is_some())
+            .map(|n| {
+                (
+                    n.Path.clone(),
+                    format!("{}{}", new, &n.Path[existing.len()..]),
+                )
+            })
+            .collect()
+    }
+
+    /// The mailboxes to DELETE, children first, to remove the subtree at
+    /// `path`. DELETE leaves inferiors in place, so each one has to go
+    /// before its parent. Synthesized and `\NonExistent` levels are skipped.
+    pub fn PlanDelete(&self, path: &str) -> Vec<String> {
+        let node = match self.Get(path) {
+            Some(node) => node,
+            None => return Vec::new(),
+        };
+
+        node.Descendants()
+            .into_iter()
+            .rev()
+            .filter(|n| {
+                n.Info
+                    .as_ref()
+                    .map_or(false, |i| !i.Has(&MailboxAttribute::NonExistent))
+            })
+            .map(|n| n.Path.clone())
+            .collect()
+    }
+}
+
+impl<'t> MailboxNode<'t> {
+    /// Reports whether the node was added for a level the server didn't list.
+    pub fn IsSynthesized(&self) -> bool {
+        self.Info.is_none()
+    }
+
+    /// The node and everything under it, parents before their children.
+    pub fn Descendants(&self) -> Vec<&MailboxNode<'t>> {
+        let mut nodes = Vec::new();
+        self.walk(&mut nodes);
+        nodes
+    }
+
+    fn walk<'n>(&'n self, nodes: &mut Vec<&'n MailboxNode<'t>>) {
+        nodes.push(self);
+        for child in self.Children.iter() {
+            child.walk(nodes);
+        }
+    }
+}
+
+fn find<'n, 't>(nodes: &'n [MailboxNode<'t>], path: &str) -> Option<&'n MailboxNode<'t>> {
+    for node in nodes {
+        if node.Path == path {
+            return Some(node);
+        }
+
+        // Only descend into the branch the path goes through
+        let below = !node.Delimiter.is_empty()
+            && path
+                .strip_prefix(node.Path.as_str())
+                .map_or(false, |rest| rest.starts_with(node.Delimiter.as_str()));
+        if below {
+            return find(&node.Children, path);
+        }
+    }
+
+    None
+}
@@ -0,0 +1,110 @@
+use std::{fmt, ops, str::FromStr};
+
+use futures_lite::io;
+
+/// A set of access rights of the ACL extension (RFC 4314), e.g. `lrswi`.
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct Rights(u32);
+
+/// The letters of the rights, in the order of their bits. `c` and `d` are
+/// the RFC 2086 rights, still sent by some servers.
+const letters: &str = "lrswipkxteacd0123456789";
+
+impl Rights {
+    /// `l`: the mailbox is visible to LIST.
+    pub const Lookup: Rights = Rights(1 << 0);
+    /// `r`: SELECT, EXAMINE, FETCH, SEARCH and COPY from the mailbox.
+    pub const Read: Rights = Rights(1 << 1);
+    /// `s`: keep the \Seen flag across sessions.
+    pub const Seen: Rights = Rights(1 << 2);
+    /// `w`: set flags other than \Seen and \Deleted.
+    pub const Write: Rights = Rights(1 << 3);
+    /// `i`: APPEND and COPY into the mailbox.
+    pub const Insert: Rights = Rights(1 << 4);
+    /// `p`: send mail to the submission address of the mailbox.
+    pub const Post: Rights = Rights(1 << 5);
+    /// `k`: create mailboxes under this one, or rename into it.
+    pub const CreateMailbox: Rights = Rights(1 << 6);
+    /// `x`: delete or rename the mailbox.
+    pub const DeleteMailbox: Rights = Rights(1 << 7);
+    /// `t`: set or clear the \Deleted flag.
+    pub const DeleteMessages: Rights = Rights(1 << 8);
+    /// `e`: EXPUNGE.
+    pub const Expunge: Rights = Rights(1 << 9);
+    /// `a`: administer the ACL of the mailbox.
+    pub const Administer: Rights = Rights(1 << 10);
+    /// `c`: RFC 2086 create right, see `Expanded`.
+    pub const Create: Rights = Rights(1 << 11);
+    /// `d`: RFC 2086 delete right, see `Expanded`.
+    pub const Delete: Rights = Rights(1 << 12);
+
+    pub fn IsEmpty(&self) -> bool {
+        self.0 == 0
+    }
+
+    /// Reports whether every right of `other` is in the set.
+    pub fn Contains(&self, other: Rights) -> bool {
+        self.0 & other.0 == other.0
+    }
+
+    pub fn Add(&mut self, other: Rights) {
+        self.0 |= other.0
+    }
+
+    pub fn Remove(&mut self, other: Rights) {
+        self.0 &= !other.0
+    }
+
+    /// Replaces the obsolete `c` and `d` rights with the ones they stand
+    /// for in RFC 4314: `c` with `k`, and `d` with `x`, `t` and `e`.
+    pub fn Expanded(&self) -> Rights {
+        let mut rights = *self;
+        if self.Contains(Rights::Create) {
+            rights.Remove(Rights::Create);
+            rights.Add(Rights::CreateMailbox);
+        }
+        if self.Contains(Rights::Delete) {
+            rights.Remove(Rights::Delete);
+            rights.Add(Rights::DeleteMailbox | Rights::DeleteMessages | Rights::Expunge);
+        }
+        rights
+    }
+}
+
+impl ops::BitOr for Rights {
+    type Output = Rights;
+
+    fn bitor(self, other: Rights) -> Rights {
+        Rights(self.0 | other.0)
+    }
+}
+
+impl fmt::Display for Rights {
+    fn fmt(&self, fmt: &mut fmt::Formatter) -> fmt::Result {
+        for (i, c) in letters.chars().enumerate() {
+            if self.0 & 1 << i != 0 {
+                write!(fmt, "{}", c)?;
+            }
+        }
+
+        Ok(())
+    }
+}
+
+impl FromStr for Rights {
+    type Err = io::Error;
+
+    /// Parses rights such as `lrswipkxtea`. Digits are rights left for
+    /// implementations to define. Letters of rights this crate doesn't
+    /// know, such as the `n` Cyrus sends, are skipped.
+    fn from_str(s: &str) -> io::Result<Rights> {
+        let mut rights = Rights::default();
+        for c in s.chars() {
+            if let Some(i) = letters.find(c.to_ascii_lowercase()) {
+                rights.0 |= 1 << i;
+            }
+        }
+
+        Ok(rights)
+    }
+}
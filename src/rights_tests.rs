@@ -0,0 +1,28 @@
+use crate::Rights;
+
+#[test]
+fn TestRights_Parse() {
+    let rights = "lrswite".parse::<Rights>().unwrap();
+    debug_assert!(rights.Contains(Rights::Lookup | Rights::Read | Rights::Expunge));
+    debug_assert!(!rights.Contains(Rights::Administer));
+    debug_assert!(rights.to_string() == "lrswite");
+
+    debug_assert!("".parse::<Rights>().map_or(false, |r| r.IsEmpty()));
+    debug_assert!("lr0"
+        .parse::<Rights>()
+        .map_or(false, |r| r.to_string() == "lr0"));
+    debug_assert!("lrnz"
+        .parse::<Rights>()
+        .map_or(false, |r| r == Rights::Lookup | Rights::Read));
+}
+
+#[test]
+fn TestRights_Expanded() {
+    let rights = "lrcd".parse::<Rights>().unwrap().Expanded();
+    debug_assert!(rights.to_string() == "lrkxte");
+
+    let mut rights = Rights::Read | Rights::Write;
+    rights.Remove(Rights::Write);
+    rights.Add(Rights::Insert);
+    debug_assert!(rights == Rights::Read | Rights::Insert);
+}
@@ -0,0 +1,318 @@
+use std::{fmt, ops::RangeInclusive, str::FromStr};
+
+use futures_lite::io;
+
+/// One end of a sequence range (`seq-number` in RFC 3501): a number, or `*`
+/// for the largest number in use in the mailbox.
+///
+/// `*` sorts after every number, whatever the mailbox size turns out to be.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub enum SeqNumber {
+    Number(u32),
+    Star,
+}
+
+impl SeqNumber {
+    fn resolve(self, max: u32) -> u32 {
+        match self {
+            SeqNumber::Number(n) => n,
+            SeqNumber::Star => max,
+        }
+    }
+}
+
+impl fmt::Display for SeqNumber {
+    fn fmt(&self, fmt: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            SeqNumber::Number(n) => write!(fmt, "{}", n),
+            SeqNumber::Star => fmt.write_str("*"),
+        }
+    }
+}
+
+impl FromStr for SeqNumber {
+    type Err = io::Error;
+
+    fn from_str(s: &str) -> io::Result<SeqNumber> {
+        if s == "*" {
+            return Ok(SeqNumber::Star);
+        }
+
+        match s.parse::<u32>() {
+            Ok(0) | Err(_) => Err(io::Error::new(
+                io::ErrorKind::Other,
+                format!("invalid sequence number: {:?}", s),
+            )),
+            Ok(n) => Ok(SeqNumber::Number(n)),
+        }
+    }
+}
+
+/// A single `n` or `n:m` element of a sequence set.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct Seq {
+    pub Start: SeqNumber,
+    pub Stop: SeqNumber,
+}
+
+impl Seq {
+    pub fn new(a: u32, b: u32) -> Seq {
+        Seq::ordered(SeqNumber::Number(a), SeqNumber::Number(b))
+    }
+
+    fn ordered(a: SeqNumber, b: SeqNumber) -> Seq {
+        if a <= b {
+            Seq { Start: a, Stop: b }
+        } else {
+            Seq { Start: b, Stop: a }
+        }
+    }
+
+    /// Reports whether `id` is in the range, taking `*` as larger than any
+    /// number.
+    pub fn Contains(&self, id: u32) -> bool {
+        self.Start <= SeqNumber::Number(id) && SeqNumber::Number(id) <= self.Stop
+    }
+
+    pub fn Dynamic(&self) -> bool {
+        self.Start == SeqNumber::Star || self.Stop == SeqNumber::Star
+    }
+
+    /// Replaces `*` with `max` and re-orders the bounds if needed.
+    pub fn Resolve(&self, max: u32) -> Seq {
+        Seq::new(self.Start.resolve(max), self.Stop.resolve(max))
+    }
+
+    /// The ids of the range, `None` if it refers to `*`.
+    fn range(&self) -> Option<RangeInclusive<u32>> {
+        match (self.Start, self.Stop) {
+            (SeqNumber::Number(a), SeqNumber::Number(b)) => Some(a..=b),
+            _ => None,
+        }
+    }
+}
+
+impl fmt::Display for Seq {
+    fn fmt(&self, fmt: &mut fmt::Formatter) -> fmt::Result {
+        if self.Start == self.Stop {
+            self.Start.fmt(fmt)
+        } else {
+            write!(fmt, "{}:{}", self.Start, self.Stop)
+        }
+    }
+}
+
+impl FromStr for Seq {
+    type Err = io::Error;
+
+    fn from_str(s: &str) -> io::Result<Seq> {
+        Ok(match s.split_once(':') {
+            Some((a, b)) => Seq::ordered(a.parse()?, b.parse()?),
+            None => {
+                let n = s.parse()?;
+                Seq { Start: n, Stop: n }
+            }
+        })
+    }
+}
+
+/// A set of message sequence numbers or UIDs, as in `1:5,7,10:*`.
+///
+/// Elements are kept in the order they were parsed or added, which matters
+/// for response codes such as COPYUID where two sets are matched up pairwise.
+/// Use `Compact` to get the minimal sorted form.
+#[derive(Debug, Default, Clone, PartialEq, Eq, Hash)]
+pub struct SequenceSet {
+    pub Set: Vec<Seq>,
+}
+
+impl SequenceSet {
+    /// Builds the smallest set of ranges covering `ids`.
+    pub fn FromIds<I: IntoIterator<Item = u32>>(ids: I) -> SequenceSet {
+        let mut set = SequenceSet {
+            Set: ids.into_iter().map(|n| Seq::new(n, n)).collect(),
+        };
+        set.Compact();
+        set
+    }
+
+    pub fn IsEmpty(&self) -> bool {
+        self.Set.is_empty()
+    }
+
+    /// Reports whether the set refers to `*` and so depends on the mailbox size.
+    pub fn Dynamic(&self) -> bool {
+        self.Set.iter().any(Seq::Dynamic)
+    }
+
+    pub fn Add(&mut self, id: u32) {
+        self.AddRange(id, id)
+    }
+
+    pub fn AddRange(&mut self, start: u32, stop: u32) {
+        self.Set.push(Seq::new(start, stop))
+    }
+
+    pub fn Contains(&self, id: u32) -> bool {
+        self.Set.iter().any(|s| s.Contains(id))
+    }
+
+    /// Sorts the ranges and merges the ones that overlap or touch.
+    pub fn Compact(&mut self) {
+        self.Set.sort();
+
+        let mut out: Vec<Seq> = Vec::with_capacity(self.Set.len());
+        for s in self.Set.drain(..) {
+            match out.last_mut() {
+                Some(last) if touches(last, &s) => {
+                    last.Stop = last.Stop.max(s.Stop);
+                }
+                _ => out.push(s),
+            }
+        }
+
+        self.Set = out;
+    }
+
+    /// Replaces `*` with `max`, the number of messages or the highest UID in
+    /// the mailbox, and compacts the result.
+    pub fn Resolve(&self, max: u32) -> SequenceSet {
+        let mut set = SequenceSet {
+            Set: self.Set.iter().map(|s| s.Resolve(max)).collect(),
+        };
+        set.Compact();
+        set
+    }
+
+    /// Iterates every id in the set in ascending order, with `*` standing
+    /// for `max`.
+    pub fn Iter(&self, max: u32) -> impl Iterator<Item = u32> {
+        self.Resolve(max)
+            .Set
+            .into_iter()
+            .flat_map(|s| s.range())
+            .flatten()
+    }
+
+    /// Iterates the ids in the order they appear in the set, without sorting
+    /// or de-duplicating them. Ranges are expanded from low to high.
+    ///
+    /// Fails if the set contains `*`, `Resolve` it first.
+    pub fn Ids(&self) -> io::Result<impl Iterator<Item = u32> + '_> {
+        self.requireStatic()?;
+        Ok(self.Set.iter().flat_map(Seq::range).flatten())
+    }
+
+    pub fn Union(&self, other: &SequenceSet) -> SequenceSet {
+        let mut set = SequenceSet {
+            Set: self.Set.iter().chain(other.Set.iter()).copied().collect(),
+        };
+        set.Compact();
+        set
+    }
+
+    /// The ids of `self` that are not in `other`. Whether the ids `*`
+    /// stands for are in the other set depends on the mailbox size, so
+    /// fails if either set contains `*`, `Resolve` them first.
+    pub fn Difference(&self, other: &SequenceSet) -> io::Result<SequenceSet> {
+        self.requireStatic()?;
+        other.requireStatic()?;
+
+        let ranges = |set: &SequenceSet| -> Vec<RangeInclusive<u32>> {
+            let mut set = set.clone();
+            set.Compact();
+            set.Set.iter().flat_map(Seq::range).collect()
+        };
+        let other = ranges(other);
+
+        let mut out = SequenceSet::default();
+        for s in ranges(self) {
+            let (mut start, stop) = s.into_inner();
+            let mut left = true;
+            for o in other.iter() {
+                if *o.end() < start || *o.start() > stop {
+                    continue;
+                }
+
+                if *o.start() > start {
+                    out.AddRange(start, o.start() - 1);
+                }
+
+                if *o.end() >= stop {
+                    left = false;
+                    break;
+                }
+
+                start = o.end() + 1;
+            }
+
+            if left {
+                out.AddRange(start, stop);
+            }
+        }
+
+        Ok(out)
+    }
+
+    fn requireStatic(&self) -> io::Result<()> {
+        if self.Dynamic() {
+            return Err(io::Error::new(
+                io::ErrorKind::Other,
+                format!("sequence set {} refers to *", self),
+            ));
+        }
+        Ok(())
+    }
+}
+
+/// Whether `next`, sorting after `last`, overlaps or directly follows it.
+fn touches(last: &Seq, next: &Seq) -> bool {
+    match last.Stop {
+        SeqNumber::Star => true,
+        SeqNumber::Number(n) => next.Start <= SeqNumber::Number(n.saturating_add(1)),
+    }
+}
+
+impl fmt::Display for SequenceSet {
+    fn fmt(&self, fmt: &mut fmt::Formatter) -> fmt::Result {
+        for (i, s) in self.Set.iter().enumerate() {
+            if i > 0 {
+                fmt.write_str(",")?;
+            }
+            s.fmt(fmt)?;
+        }
+
+        Ok(())
+    }
+}
+
+impl FromStr for SequenceSet {
+    type Err = io::Error;
+
+    fn from_str(s: &str) -> io::Result<SequenceSet> {
+        if s.is_empty() {
+            return Err(io::Error::new(
+                io::ErrorKind::Other,
+                "sequence set is empty",
+            ));
+        }
+
+        Ok(SequenceSet {
+            Set: s.split(',').map(str::parse).collect::<io::Result<_>>()?,
+        })
+    }
+}
+
+impl From<u32> for SequenceSet {
+    fn from(id: u32) -> SequenceSet {
+        SequenceSet {
+            Set: vec![Seq::new(id, id)],
+        }
+    }
+}
+
+impl From<Seq> for SequenceSet {
+    fn from(s: Seq) -> SequenceSet {
+        SequenceSet { Set: vec![s] }
+    }
+}
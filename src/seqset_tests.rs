@@ -0,0 +1,98 @@
+use crate::seqset::{Seq, SeqNumber, SequenceSet};
+
+#[test]
+fn TestSequenceSet_Parse() {
+    let set = "1:5,7,10:*".parse::<SequenceSet>().unwrap();
+    debug_assert!(
+        set.Set
+            == [
+                Seq::new(1, 5),
+                Seq::new(7, 7),
+                Seq {
+                    Start: SeqNumber::Number(10),
+                    Stop: SeqNumber::Star
+                }
+            ]
+    );
+    debug_assert!(set.Dynamic());
+
+    debug_assert!("5:1".parse::<SequenceSet>().unwrap().Set == [Seq::new(1, 5)]);
+    debug_assert!(
+        "*:4".parse::<SequenceSet>().unwrap().Set
+            == [Seq {
+                Start: SeqNumber::Number(4),
+                Stop: SeqNumber::Star
+            }]
+    );
+
+    // The largest UID is a number like any other, not `*`
+    let set = "4294967295".parse::<SequenceSet>().unwrap();
+    debug_assert!(!set.Dynamic() && set.to_string() == "4294967295");
+    let star = "*".parse::<SequenceSet>().unwrap();
+    debug_assert!(set.Union(&star).to_string() == "4294967295,*");
+
+    debug_assert!("".parse::<SequenceSet>().is_err());
+    debug_assert!("0".parse::<SequenceSet>().is_err());
+    debug_assert!("1,,2".parse::<SequenceSet>().is_err());
+    debug_assert!("1:a".parse::<SequenceSet>().is_err());
+}
+
+#[test]
+fn TestSequenceSet_Format() {
+    for s in ["1", "*", "1:5,7,10:*", "304,319:320"] {
+        debug_assert!(s.parse::<SequenceSet>().unwrap().to_string() == s);
+    }
+
+    debug_assert!("4:2".parse::<SequenceSet>().unwrap().to_string() == "2:4");
+}
+
+#[test]
+fn TestSequenceSet_FromIds() {
+    debug_assert!(SequenceSet::FromIds([7, 1, 2, 3, 3, 9, 8, 12]).to_string() == "1:3,7:9,12");
+    debug_assert!(SequenceSet::FromIds([]).IsEmpty());
+}
+
+#[test]
+fn TestSequenceSet_Iter() {
+    let set = "1:3,5,*".parse::<SequenceSet>().unwrap();
+    debug_assert!(set.Iter(6).collect::<Vec<_>>() == [1, 2, 3, 5, 6]);
+    debug_assert!(set.Iter(2).collect::<Vec<_>>() == [1, 2, 3, 5]);
+
+    let set = "5:*".parse::<SequenceSet>().unwrap();
+    debug_assert!(set.Iter(3).collect::<Vec<_>>() == [3, 4, 5]);
+
+    let set = "20,3:4".parse::<SequenceSet>().unwrap();
+    debug_assert!(set.Ids().unwrap().collect::<Vec<_>>() == [20, 3, 4]);
+
+    let set = "1:*".parse::<SequenceSet>().unwrap();
+    debug_assert!(set.Ids().is_err());
+    debug_assert!(set.Resolve(3).Ids().unwrap().collect::<Vec<_>>() == [1, 2, 3]);
+}
+
+#[test]
+fn TestSequenceSet_Union() {
+    let a = "1:3,10".parse::<SequenceSet>().unwrap();
+    let b = "4:6,9,20:*".parse::<SequenceSet>().unwrap();
+    debug_assert!(a.Union(&b).to_string() == "1:6,9:10,20:*");
+}
+
+#[test]
+fn TestSequenceSet_Difference() {
+    let a = "1:10,20:30".parse::<SequenceSet>().unwrap();
+    let b = "3,5:7,10:25".parse::<SequenceSet>().unwrap();
+    debug_assert!(a.Difference(&b).unwrap().to_string() == "1:2,4,8:9,26:30");
+
+    debug_assert!(a.Difference(&SequenceSet::default()).unwrap() == a);
+    debug_assert!(a
+        .Difference(&"1:4294967295".parse().unwrap())
+        .unwrap()
+        .IsEmpty());
+
+    // What `*` stands for decides the result, so the sets must be resolved
+    let a = "1:*".parse::<SequenceSet>().unwrap();
+    let b = "5:*".parse::<SequenceSet>().unwrap();
+    debug_assert!(a.Difference(&b).is_err());
+    debug_assert!(b.Difference(&"1:3".parse().unwrap()).is_err());
+    debug_assert!(a.Resolve(8).Difference(&b.Resolve(8)).unwrap().to_string() == "1:4");
+    debug_assert!(a.Resolve(3).Difference(&b.Resolve(3)).unwrap().to_string() == "1:2");
+}
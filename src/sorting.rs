@@ -0,0 +1,84 @@
+//! Client-side sorting, for servers without SORT. Orders messages the way
+//! RFC 5256 and RFC 5957 define the sort keys.
+
+use std::cmp::Ordering;
+
+use crate::{
+    commands::{SortCriterion, SortKey},
+    header,
+    responses::{Address, Message},
+    threading,
+};
+
+/// The mailbox of the first address, compared case-insensitively.
+fn mailbox(addresses: Option<&Vec<Address>>) -> String {
+    addresses
+        .and_then(|a| a.first())
+        .and_then(|a| a.Mailbox.as_deref())
+        .unwrap_or("")
+        .to_ascii_uppercase()
+}
+
+/// The display name of the first address, or its address if it has no
+/// name (RFC 5957).
+fn display(addresses: Option<&Vec<Address>>) -> String {
+    let addr = match addresses.and_then(|a| a.first()) {
+        Some(addr) => addr,
+        None => return String::new(),
+    };
+
+    let name = addr.Name.as_deref().map(header::DecodeWords);
+    match name.filter(|n| !n.trim().is_empty()) {
+        Some(name) => name.trim().to_lowercase(),
+        None => addr.Email().unwrap_or_default().to_lowercase(),
+    }
+}
+
+fn compare(a: &Message<'_>, b: &Message<'_>, key: SortKey) -> Ordering {
+    let (ea, eb) = (a.Envelope.as_ref(), b.Envelope.as_ref());
+    match key {
+        SortKey::Arrival => a.ArrivalDate().cmp(&b.ArrivalDate()),
+        SortKey::Date => a.SentDate().cmp(&b.SentDate()),
+        SortKey::Size => a.Size.cmp(&b.Size),
+        SortKey::Subject => {
+            let subject = |e: Option<&crate::responses::Envelope>| {
+                threading::BaseSubject(e.and_then(|e| e.Subject.as_deref()).unwrap_or(""))
+                    .to_ascii_uppercase()
+            };
+            subject(ea).cmp(&subject(eb))
+        }
+        SortKey::From => mailbox(ea.map(|e| &e.From)).cmp(&mailbox(eb.map(|e| &e.From))),
+        SortKey::To => mailbox(ea.map(|e| &e.To)).cmp(&mailbox(eb.map(|e| &e.To))),
+        SortKey::Cc => mailbox(ea.map(|e| &e.Cc)).cmp(&mailbox(eb.map(|e| &e.Cc))),
+        SortKey::DisplayFrom => display(ea.map(|e| &e.From)).cmp(&display(eb.map(|e| &e.From))),
+        SortKey::DisplayTo => display(ea.map(|e| &e.To)).cmp(&display(eb.map(|e| &e.To))),
+    }
+}
+
+/// Orders messages by `criteria`, as SORT would, and returns their sequence
+/// numbers, or UIDs with `uid`. Ties are broken by sequence number.
+///
+/// The messages need the items the keys use: `Envelope`, `InternalDate`
+/// or `Size`.
+pub fn Sort(messages: &[Message<'_>], criteria: &[SortCriterion], uid: bool) -> Vec<u32> {
+    let mut sorted: Vec<&Message> = messages.iter().collect();
+    sorted.sort_by(|a, b| {
+        criteria
+            .iter()
+            .map(|c| {
+                let order = compare(a, b, c.Key);
+                if c.Reverse {
+                    order.reverse()
+                } else {
+                    order
+                }
+            })
+            .find(|o| o.is_ne())
+            .unwrap_or_else(|| a.SeqNum.cmp(&b.SeqNum))
+    });
+
+    sorted
+        .into_iter()
+        .filter_map(|m| if uid { m.Uid } else { Some(m.SeqNum) })
+        .collect()
+}
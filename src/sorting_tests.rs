@@ -0,0 +1,101 @@
+use crate::{
+    commands::{SortCriterion, SortKey},
+    header,
+    responses::{Address, Envelope, Message},
+    sorting, threading,
+};
+
+#[test]
+fn TestDecodeWords() {
+    debug_assert!(header::DecodeWords("=?UTF-8?Q?Caf=C3=A9_cr=C3=A8me?=") == "Café crème");
+    debug_assert!(header::DecodeWords("=?iso-8859-1?q?Andr=E9?= Pirard") == "André Pirard");
+    debug_assert!(header::DecodeWords("=?UTF-8?B?SGVs?= =?UTF-8?B?bG8=?= world") == "Hello world");
+    debug_assert!(header::DecodeWords("=?x-unknown?Q?a?= b") == "=?x-unknown?Q?a?= b");
+    debug_assert!(header::DecodeWords("a =? b") == "a =? b");
+    debug_assert!(threading::BaseSubject("=?UTF-8?Q?Re=3A_R=C3=A9union?= (fwd)") == "Réunion");
+}
+
+fn message(
+    seqNum: u32,
+    from: (&str, &str),
+    subject: &str,
+    date: Option<&str>,
+    arrival: &str,
+    size: u32,
+) -> Message<'static> {
+    Message {
+        SeqNum: seqNum,
+        Uid: Some(seqNum * 10),
+        Envelope: Some(Envelope {
+            Date: date.map(Into::into),
+            Subject: Some(subject.into()),
+            From: vec![Address {
+                Name: Some(from.0.into()).filter(|n: &String| !n.is_empty()),
+                Mailbox: Some(from.1.into()),
+                Host: Some("example.com".into()),
+                ..Default::default()
+            }],
+            ..Default::default()
+        }),
+        InternalDate: Some(arrival.to_string().into()),
+        Size: Some(size),
+        ..Default::default()
+    }
+}
+
+#[test]
+fn TestSort() {
+    let messages = [
+        message(
+            1,
+            ("Zoe", "alice"),
+            "Re: Budget",
+            Some("Mon, 4 Mar 2024 10:00:00 +0000"),
+            "04-Mar-2024 10:01:00 +0000",
+            300,
+        ),
+        message(
+            2,
+            ("", "bob"),
+            "[team] Agenda",
+            Some("Mon, 4 Mar 2024 11:00:00 +0200"),
+            "04-Mar-2024 09:01:00 +0000",
+            100,
+        ),
+        message(
+            3,
+            ("=?UTF-8?Q?=C3=89mile?=", "Carol"),
+            "budget",
+            None,
+            "03-Mar-2024 23:00:00 -0500",
+            200,
+        ),
+        message(
+            4,
+            ("Adam", "alice"),
+            "Fwd: agenda",
+            Some("invalid"),
+            "01-Mar-2024 08:00:00 +0000",
+            100,
+        ),
+    ];
+
+    let sort = |criteria: &[SortCriterion]| sorting::Sort(&messages, criteria, false);
+    let reverse = |key| SortCriterion {
+        Key: key,
+        Reverse: true,
+    };
+
+    debug_assert!(sort(&[SortKey::Arrival.into()]) == [4, 3, 2, 1]);
+    // 2 was sent at 09:00 UTC, 3 and 4 have no valid Date
+    debug_assert!(sort(&[SortKey::Date.into()]) == [4, 3, 2, 1]);
+    debug_assert!(sort(&[reverse(SortKey::Date)]) == [1, 2, 3, 4]);
+    debug_assert!(sort(&[SortKey::Size.into()]) == [2, 4, 3, 1]);
+    debug_assert!(sort(&[reverse(SortKey::Size)]) == [1, 3, 2, 4]);
+    debug_assert!(sort(&[SortKey::Subject.into(), reverse(SortKey::Arrival)]) == [2, 4, 1, 3]);
+    debug_assert!(sort(&[SortKey::From.into()]) == [1, 4, 2, 3]);
+    debug_assert!(sort(&[SortKey::DisplayFrom.into()]) == [4, 2, 1, 3]);
+    debug_assert!(sort(&[]) == [1, 2, 3, 4]);
+
+    debug_assert!(sorting::Sort(&messages, &[SortKey::Size.into()], true) == [20, 40, 30, 10]);
+}
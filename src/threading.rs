@@ -0,0 +1,388 @@
+//! Client-side threading, for servers without THREAD. Implements the
+//! REFERENCES algorithm of RFC 5256, based on the one by Jamie Zawinski,
+//! and gives the same `ThreadNode` trees as a THREAD response.
+
+use std::collections::HashMap;
+
+use crate::{
+    header,
+    responses::{Message, ThreadNode},
+};
+
+/// Removes the reply and forward markers of a subject, as in RFC 5256
+/// section 2.1: `Re: [list] Fwd: Hello (fwd)` becomes `Hello`. Encoded
+/// words are decoded first.
+pub fn BaseSubject(subject: &str) -> String {
+    baseSubject(subject).0
+}
+
+/// The base subject, and whether the subject had a reply or forward marker.
+fn baseSubject(subject: &str) -> (String, bool) {
+    let mut s = header::DecodeWords(subject)
+        .split_whitespace()
+        .collect::<Vec<_>>()
+        .join(" ");
+    let mut reply = false;
+
+    loop {
+        loop {
+            let trimmed = s.trim_end();
+            match trimmed.len().checked_sub(5) {
+                Some(n)
+                    if trimmed.is_char_boundary(n)
+                        && trimmed[n..].eq_ignore_ascii_case("(fwd)") =>
+                {
+                    s = trimmed[..n].to_string();
+                    reply = true;
+                }
+                _ => {
+                    s = trimmed.to_string();
+                    break;
+                }
+            }
+        }
+
+        loop {
+            let before = s.clone();
+
+            if let Some(rest) = stripLeader(&s) {
+                s = rest.to_string();
+                reply = true;
+            }
+
+            // A leading blob goes unless it is all that is left
+            if let Some(rest) = stripBlob(&s) {
+                if !rest.is_empty() {
+                    s = rest.to_string();
+                }
+            }
+
+            if s == before {
+                break;
+            }
+        }
+
+        let wrapped = s.len() > 6
+            && s.is_char_boundary(5)
+            && s[..5].eq_ignore_ascii_case("[fwd:")
+            && s.ends_with(']');
+        if !wrapped {
+            return (s, reply);
+        }
+        s = s[5..s.len() - 1].to_string();
+        reply = true;
+    }
+}
+
+/// Strips a `[blob]` at the start of `s`.
+fn stripBlob(s: &str) -> Option<&str> {
+    let s = s.trim_start().strip_prefix('[')?;
+    let end = s.find(['[', ']'])?;
+    if &s[end..end + 1] != "]" {
+        return None;
+    }
+
+    Some(s[end + 1..].trim_start())
+}
+
+/// Strips blobs followed by `re`, `fw` or `fwd`, an optional blob and a
+/// colon.
+fn stripLeader(s: &str) -> Option<&str> {
+    let mut s = s.trim_start();
+    while let Some(rest) = stripBlob(s) {
+        s = rest;
+    }
+
+    let lower = s.get(..3).unwrap_or(s).to_ascii_lowercase();
+    let marker = ["fwd", "fw", "re"]
+        .into_iter()
+        .find(|m| lower.starts_with(m))?;
+    let mut s = s[marker.len()..].trim_start();
+    if let Some(rest) = stripBlob(s) {
+        s = rest;
+    }
+
+    Some(s.strip_prefix(':')?.trim_start())
+}
+
+/// The `<...>` message IDs of a header value, such as References.
+pub fn MessageIds(value: &str) -> Vec<String> {
+    let mut ids = Vec::new();
+    let mut rest = value;
+    while let Some(start) = rest.find('<') {
+        let end = match rest[start..].find('>') {
+            Some(end) => start + end,
+            None => break,
+        };
+        ids.push(rest[start..=end].to_string());
+        rest = &rest[end + 1..];
+    }
+    ids
+}
+
+#[derive(Debug, Default)]
+struct Container {
+    message: Option<usize>,
+    parent: Option<usize>,
+    children: Vec<usize>,
+}
+
+struct Threader<'t, 'm> {
+    messages: &'t [Message<'m>],
+    containers: Vec<Container>,
+    ids: HashMap<String, usize>,
+}
+
+impl<'t, 'm> Threader<'t, 'm> {
+    fn container(&mut self, id: &str) -> usize {
+        if let Some(c) = self.ids.get(id) {
+            return *c;
+        }
+
+        self.containers.push(Container::default());
+        self.ids.insert(id.to_string(), self.containers.len() - 1);
+        self.containers.len() - 1
+    }
+
+    /// Reports whether `a` is `b` or above it.
+    fn isAncestor(&self, a: usize, b: usize) -> bool {
+        let mut c = Some(b);
+        while let Some(i) = c {
+            if i == a {
+                return true;
+            }
+            c = self.containers[i].parent;
+        }
+        false
+    }
+
+    fn link(&mut self, parent: usize, child: usize) {
+        if let Some(old) = self.containers[child].parent.take() {
+            self.containers[old].children.retain(|c| *c != child);
+        }
+        self.containers[child].parent = Some(parent);
+        self.containers[parent].children.push(child);
+    }
+
+    fn unlink(&mut self, child: usize) {
+        if let Some(old) = self.containers[child].parent.take() {
+            self.containers[old].children.retain(|c| *c != child);
+        }
+    }
+
+    /// The message a container stands for: its own, or its first child's.
+    fn message(&self, c: usize) -> Option<&'t Message<'m>> {
+        let container = &self.containers[c];
+        match container.message {
+            Some(m) => Some(&self.messages[m]),
+            None => container.children.first().and_then(|c| self.message(*c)),
+        }
+    }
+
+    fn subject(&self, c: usize) -> (String, bool) {
+        let subject = self
+            .message(c)
+            .and_then(|m| m.Envelope.as_ref())
+            .and_then(|e| e.Subject.as_deref())
+            .unwrap_or("");
+        let (base, reply) = baseSubject(subject);
+        (base.to_ascii_uppercase(), reply)
+    }
+
+    /// Step 1: links each message to the messages it references.
+    fn addMessage(&mut self, m: usize) {
+        let msg = &self.messages[m];
+        let envelope = msg.Envelope.as_ref();
+
+        // A missing or duplicate Message-ID gets a container of its own
+        let id = envelope
+            .and_then(|e| e.MessageId.as_deref())
+            .and_then(|id| MessageIds(id).into_iter().next());
+        let c = match id {
+            Some(id)
+                if self
+                    .ids
+                    .get(&id)
+                    .map_or(true, |c| self.containers[*c].message.is_none()) =>
+            {
+                self.container(&id)
+            }
+            _ => {
+                self.containers.push(Container::default());
+                self.containers.len() - 1
+            }
+        };
+        self.containers[c].message = Some(m);
+
+        let mut refs = msg
+            .HeaderField("References")
+            .map(|r| MessageIds(&r))
+            .unwrap_or_default();
+        if refs.is_empty() {
+            refs = envelope
+                .and_then(|e| e.InReplyTo.as_deref())
+                .map(MessageIds)
+                .unwrap_or_default();
+            refs.truncate(1);
+        }
+
+        let refs: Vec<usize> = refs.iter().map(|r| self.container(r)).collect();
+        for pair in refs.windows(2) {
+            let (parent, child) = (pair[0], pair[1]);
+            if self.containers[child].parent.is_none() && !self.isAncestor(child, parent) {
+                self.link(parent, child);
+            }
+        }
+
+        self.unlink(c);
+        if let Some(last) = refs.last() {
+            if !self.isAncestor(c, *last) {
+                self.link(*last, c);
+            }
+        }
+    }
+
+    /// Step 3: removes empty containers, moving their children up.
+    fn prune(&mut self, list: Vec<usize>, root: bool) -> Vec<usize> {
+        let mut kept = Vec::new();
+        for c in list {
+            let children = std::mem::take(&mut self.containers[c].children);
+            let children = self.prune(children, false);
+
+            if self.containers[c].message.is_none() && (!root || children.len() <= 1) {
+                kept.extend(children);
+                continue;
+            }
+
+            self.containers[c].children = children;
+            kept.push(c);
+        }
+        kept
+    }
+
+    /// Sorts siblings by sent date, then by sequence number. An empty
+    /// container is sorted by its first child.
+    fn sort(&mut self, list: &mut [usize]) {
+        for c in list.iter() {
+            let mut children = std::mem::take(&mut self.containers[*c].children);
+            self.sort(&mut children);
+            self.containers[*c].children = children;
+        }
+
+        list.sort_by_key(|c| {
+            self.message(*c)
+                .map(|m| (m.SentDate().unwrap_or(0), m.SeqNum))
+        });
+    }
+
+    /// Step 5: gathers the threads with the same base subject.
+    fn groupBySubject(&mut self, roots: Vec<usize>) -> Vec<usize> {
+        let mut table: HashMap<String, usize> = HashMap::new();
+        for r in roots.iter() {
+            let (subject, reply) = self.subject(*r);
+            if subject.is_empty() {
+                continue;
+            }
+
+            let replace = match table.get(&subject) {
+                None => true,
+                Some(old) => {
+                    (self.containers[*r].message.is_none()
+                        && self.containers[*old].message.is_some())
+                        || (self.subject(*old).1 && !reply)
+                }
+            };
+            if replace {
+                table.insert(subject, *r);
+            }
+        }
+
+        let mut roots: Vec<Option<usize>> = roots.into_iter().map(Some).collect();
+        for i in 0..roots.len() {
+            let r = match roots[i] {
+                Some(r) => r,
+                None => continue,
+            };
+            let (subject, reply) = self.subject(r);
+            let old = match table.get(&subject) {
+                Some(old) if *old != r && !subject.is_empty() => *old,
+                _ => continue,
+            };
+
+            let (rEmpty, oldEmpty) = (
+                self.containers[r].message.is_none(),
+                self.containers[old].message.is_none(),
+            );
+            if rEmpty && oldEmpty {
+                for child in std::mem::take(&mut self.containers[r].children) {
+                    self.containers[child].parent = None;
+                    self.link(old, child);
+                }
+            } else if oldEmpty || (reply && !self.subject(old).1) {
+                self.link(old, r);
+            } else {
+                self.containers.push(Container::default());
+                let dummy = self.containers.len() - 1;
+                self.link(dummy, old);
+                self.link(dummy, r);
+                table.insert(subject, dummy);
+
+                let pos = roots.iter().position(|c| *c == Some(old));
+                roots[pos.unwrap()] = Some(dummy);
+            }
+            roots[i] = None;
+        }
+
+        roots.into_iter().flatten().collect()
+    }
+
+    fn node(&self, c: usize, uid: bool) -> ThreadNode {
+        let container = &self.containers[c];
+        ThreadNode {
+            Id: container.message.and_then(|m| {
+                let msg = &self.messages[m];
+                if uid {
+                    msg.Uid
+                } else {
+                    Some(msg.SeqNum)
+                }
+            }),
+            Children: container
+                .children
+                .iter()
+                .map(|c| self.node(*c, uid))
+                .collect(),
+        }
+    }
+}
+
+/// Threads messages with the REFERENCES algorithm. The messages need their
+/// `Envelope`, and their References field in `Header`; threads are
+/// identified by UID with `uid`.
+pub fn References(messages: &[Message<'_>], uid: bool) -> Vec<ThreadNode> {
+    let mut order: Vec<usize> = (0..messages.len()).collect();
+    order.sort_by_key(|m| messages[*m].SeqNum);
+
+    let mut threader = Threader {
+        messages,
+        containers: Vec::new(),
+        ids: HashMap::new(),
+    };
+    for m in order {
+        threader.addMessage(m);
+    }
+
+    let roots = (0..threader.containers.len())
+        .filter(|c| threader.containers[*c].parent.is_none())
+        .collect();
+    let mut roots = threader.prune(roots, true);
+    for c in roots.iter() {
+        threader.containers[*c].parent = None;
+    }
+
+    threader.sort(&mut roots);
+    let mut roots = threader.groupBySubject(roots);
+    threader.sort(&mut roots);
+
+    roots.iter().map(|c| threader.node(*c, uid)).collect()
+}
@@ -0,0 +1,122 @@
+use crate::{
+    date,
+    responses::{Envelope, Message, ThreadNode},
+    threading::{self, BaseSubject, MessageIds},
+};
+
+#[test]
+fn TestBaseSubject() {
+    for (subject, base) in [
+        ("Hello", "Hello"),
+        ("Re: Hello", "Hello"),
+        ("RE:  re: Fwd:Hello  ", "Hello"),
+        ("Fw: Hello (fwd)", "Hello"),
+        ("[list] Re: [other] Hello", "Hello"),
+        ("Re [list]: Hello", "Hello"),
+        ("[Fwd: Re: Hello]", "Hello"),
+        ("[list]", "[list]"),
+        ("Regarding\tthe\r\n plan", "Regarding the plan"),
+        ("", ""),
+    ] {
+        debug_assert!(BaseSubject(subject) == base, "{:?}", subject);
+    }
+}
+
+#[test]
+fn TestMessageIds() {
+    debug_assert!(MessageIds("<a@x> (comment)\r\n <b@y><c@z>") == ["<a@x>", "<b@y>", "<c@z>"]);
+    debug_assert!(MessageIds("none").is_empty());
+}
+
+#[test]
+fn TestParseDate() {
+    debug_assert!(date::Parse("Thu, 1 Jan 1970 00:00:00 +0000") == Some(0));
+    debug_assert!(date::Parse("Mon, 7 Feb 1994 21:52:25 -0800") == Some(760686745));
+    debug_assert!(date::Parse("7 Feb 94 21:52 PST (Pacific)") == Some(760686720));
+    debug_assert!(date::Parse("29 Feb 2000 12:00:00 +0130") == Some(951820200));
+    debug_assert!(date::Parse("yesterday").is_none());
+}
+
+fn message(seqNum: u32, id: &str, subject: &str, date: &str, references: &str) -> Message<'static> {
+    Message {
+        SeqNum: seqNum,
+        Uid: Some(seqNum + 100),
+        Envelope: Some(Envelope {
+            Date: Some(date.into()),
+            Subject: Some(subject.into()),
+            MessageId: Some(id.into()),
+            ..Default::default()
+        }),
+        Header: Some(format!("References: {}\r\n\r\n", references).into()),
+        ..Default::default()
+    }
+}
+
+fn node(id: u32, children: Vec<ThreadNode>) -> ThreadNode {
+    ThreadNode {
+        Id: Some(id),
+        Children: children,
+    }
+}
+
+#[test]
+fn TestReferences() {
+    let messages = [
+        message(1, "<1@x>", "Plan", "1 Mar 2024 10:00 +0000", ""),
+        message(2, "<2@x>", "Re: Plan", "1 Mar 2024 12:00 +0000", "<1@x>"),
+        // Replies to a message that wasn't fetched
+        message(3, "<3@x>", "Re: Lunch", "1 Mar 2024 09:00 +0000", "<0@x>"),
+        message(4, "<4@x>", "Re: Lunch", "1 Mar 2024 09:30 +0000", "<0@x>"),
+        message(5, "<5@x>", "Re: Plan", "1 Mar 2024 11:00 +0000", "<1@x>"),
+        message(
+            6,
+            "<6@x>",
+            "Re: Re: Plan",
+            "1 Mar 2024 13:00 +0000",
+            "<1@x> <2@x>",
+        ),
+        // Same subject, no references
+        message(7, "<7@x>", "Re: Plan", "2 Mar 2024 08:00 +0000", ""),
+        message(8, "<8@x>", "Other", "28 Feb 2024 08:00 +0000", ""),
+    ];
+
+    debug_assert!(
+        threading::References(&messages, false)
+            == [
+                node(8, vec![]),
+                ThreadNode {
+                    Id: None,
+                    Children: vec![node(3, vec![]), node(4, vec![])],
+                },
+                node(
+                    1,
+                    vec![
+                        node(5, vec![]),
+                        node(2, vec![node(6, vec![])]),
+                        node(7, vec![]),
+                    ]
+                ),
+            ]
+    );
+
+    let threads = threading::References(&messages[..2], true);
+    debug_assert!(threads == [node(101, vec![node(102, vec![])])]);
+}
+
+#[test]
+fn TestReferences_Loop() {
+    let messages = [
+        message(1, "<1@x>", "A", "1 Mar 2024 10:00 +0000", "<2@x>"),
+        message(2, "<2@x>", "B", "1 Mar 2024 11:00 +0000", "<1@x>"),
+        message(3, "<3@x>", "C", "1 Mar 2024 12:00 +0000", "<3@x>"),
+    ];
+
+    let threads = threading::References(&messages, false);
+    debug_assert!(threads.iter().map(ThreadNode::Ids).flatten().count() == 3);
+}
+
+#[test]
+fn TestParseDate_InternalDate() {
+    debug_assert!(date::Parse("17-Jul-1996 02:44:25 -0700") == Some(837596665));
+    debug_assert!(date::Parse(" 1-Jan-1970 00:00:00 +0000") == Some(0));
+}
@@ -0,0 +1,139 @@
+//! Modified UTF-7, the encoding of international mailbox names (RFC 3501
+//! section 5.1.3).
+//!
+//! Printable ASCII stands for itself, except `&` which is written `&-`.
+//! Anything else is written as UTF-16 in a variant of base64 using `,`
+//! instead of `/`, between `&` and `-`.
+
+use std::borrow::Cow;
+
+use futures_lite::io;
+
+const alphabet: &[u8; 64] = b"ABCDEFGHIJKLMNOPQRSTUVWXYZabcdefghijklmnopqrstuvwxyz0123456789+,";
+
+fn printable(c: char) -> bool {
+    ('\x20'..='\x7e').contains(&c)
+}
+
+pub fn Encode(name: &str) -> String {
+    let mut out = String::with_capacity(name.len());
+    let mut units = Vec::new();
+
+    for c in name.chars() {
+        if printable(c) {
+            flush(&mut out, &mut units);
+            if c == '&' {
+                out.push_str("&-");
+            } else {
+                out.push(c);
+            }
+        } else {
+            units.extend_from_slice(c.encode_utf16(&mut [0; 2]));
+        }
+    }
+    flush(&mut out, &mut units);
+
+    out
+}
+
+/// Writes the pending UTF-16 code units as a `&...-` section.
+fn flush(out: &mut String, units: &mut Vec<u16>) {
+    if units.is_empty() {
+        return;
+    }
+
+    let bytes: Vec<u8> = units.drain(..).flat_map(u16::to_be_bytes).collect();
+
+    out.push('&');
+    for chunk in bytes.chunks(3) {
+        let n = chunk
+            .iter()
+            .enumerate()
+            .fold(0u32, |n, (i, b)| n | (*b as u32) << (16 - 8 * i));
+
+        // No padding: 1 byte takes 2 characters, 2 bytes take 3
+        for i in 0..chunk.len() + 1 {
+            out.push(alphabet[(n >> (18 - 6 * i) & 0x3f) as usize] as char);
+        }
+    }
+    out.push('-');
+}
+
+pub fn Decode(name: &str) -> io::Result<String> {
+    let invalid = || {
+        io::Error::new(
+            io::ErrorKind::Other,
+            format!("invalid modified UTF-7 mailbox name: {:?}", name),
+        )
+    };
+
+    let mut out = String::with_capacity(name.len());
+    let mut chars = name.chars();
+
+    while let Some(c) = chars.next() {
+        if !printable(c) {
+            return Err(invalid());
+        }
+
+        if c != '&' {
+            out.push(c);
+            continue;
+        }
+
+        let mut bits = 0u32;
+        let mut nbits = 0;
+        let mut units = Vec::new();
+        let mut closed = false;
+
+        for c in chars.by_ref() {
+            if c == '-' {
+                closed = true;
+                break;
+            }
+
+            let v = alphabet
+                .iter()
+                .position(|a| *a as char == c)
+                .ok_or_else(invalid)?;
+            bits = bits << 6 | v as u32;
+            nbits += 6;
+
+            if nbits >= 16 {
+                nbits -= 16;
+                units.push((bits >> nbits) as u16);
+                bits &= (1 << nbits) - 1;
+            }
+        }
+
+        // Leftover bits must be zero padding of the last character
+        if !closed || nbits >= 6 || bits != 0 {
+            return Err(invalid());
+        }
+
+        if units.is_empty() {
+            out.push('&');
+            continue;
+        }
+
+        for c in char::decode_utf16(units) {
+            out.push(c.map_err(|_| invalid())?);
+        }
+    }
+
+    Ok(out)
+}
+
+/// Decodes a mailbox name from a response, keeping the name as is if it is
+/// not valid modified UTF-7, as some servers send raw 8-bit names. With
+/// `utf8`, when UTF8=ACCEPT is enabled, names are sent as UTF-8 and not
+/// decoded at all.
+pub fn DecodeName<'n>(name: &Cow<'n, str>, utf8: bool) -> Cow<'n, str> {
+    if utf8 || !name.contains('&') {
+        return name.clone();
+    }
+
+    match Decode(name) {
+        Ok(decoded) => Cow::Owned(decoded),
+        Err(_) => name.clone(),
+    }
+}
@@ -0,0 +1,40 @@
+use crate::utf7;
+
+#[test]
+fn TestEncode() {
+    debug_assert!(utf7::Encode("INBOX") == "INBOX");
+    debug_assert!(utf7::Encode("R&D") == "R&-D");
+    debug_assert!(utf7::Encode("Entwürfe") == "Entw&APw-rfe");
+    debug_assert!(utf7::Encode("受信箱") == "&U9dP4Xux-");
+    debug_assert!(utf7::Encode("~peter/mail/台北/日本語") == "~peter/mail/&U,BTFw-/&ZeVnLIqe-");
+    // Characters outside the BMP are sent as surrogate pairs
+    debug_assert!(utf7::Encode("📧") == "&2D3c5w-");
+}
+
+#[test]
+fn TestDecode() {
+    for name in [
+        "INBOX",
+        "R&D",
+        "Entwürfe",
+        "受信箱",
+        "~peter/mail/台北/日本語",
+        "📧 a&b",
+    ] {
+        debug_assert!(utf7::Decode(&utf7::Encode(name)).unwrap() == name);
+    }
+
+    debug_assert!(utf7::Decode("&U,BTFw-").unwrap() == "台北");
+    debug_assert!(utf7::Decode("&-").unwrap() == "&");
+
+    // Unterminated, invalid base64, non-zero padding bits, lone surrogate
+    // and raw 8-bit names are rejected
+    for name in ["&U,BTFw", "&U!-", "&APx-", "&2D0-", "Entwürfe"] {
+        debug_assert!(utf7::Decode(name).is_err(), "{}", name);
+    }
+
+    debug_assert!(utf7::DecodeName(&"R&D".into(), false) == "R&D");
+    debug_assert!(utf7::DecodeName(&"Entw&APw-rfe".into(), false) == "Entwürfe");
+    // With UTF8=ACCEPT, `&` is just a character
+    debug_assert!(utf7::DecodeName(&"Entw&APw-rfe".into(), true) == "Entw&APw-rfe");
+}